@@ -2,6 +2,19 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
 mod m20220101_000002_create_draft_table;
+mod m20220101_000003_add_draft_format;
+mod m20220101_000004_create_series_table;
+mod m20220101_000005_add_series_games;
+mod m20220101_000006_create_live_draft_table;
+mod m20220101_000007_add_draft_time_banks;
+mod m20220101_000008_add_draft_auto_filled;
+mod m20220101_000009_create_draft_event_table;
+mod m20220101_000010_create_scenario_table;
+mod m20220101_000011_add_fork_columns;
+mod m20220101_000012_add_draft_event_referee;
+mod m20220101_000013_add_draft_team_details;
+mod m20220101_000014_add_draft_settings;
+mod m20220101_000015_add_draft_disabled_champions;
 
 pub struct Migrator;
 
@@ -10,7 +23,20 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20220101_000001_create_table::ChampionsMigration),
-            Box::new(m20220101_000002_create_draft_table::DraftMigration)
+            Box::new(m20220101_000002_create_draft_table::DraftMigration),
+            Box::new(m20220101_000003_add_draft_format::DraftFormatMigration),
+            Box::new(m20220101_000004_create_series_table::SeriesMigration),
+            Box::new(m20220101_000005_add_series_games::SeriesGamesMigration),
+            Box::new(m20220101_000006_create_live_draft_table::LiveDraftMigration),
+            Box::new(m20220101_000007_add_draft_time_banks::DraftTimeBanksMigration),
+            Box::new(m20220101_000008_add_draft_auto_filled::DraftAutoFilledMigration),
+            Box::new(m20220101_000009_create_draft_event_table::DraftEventMigration),
+            Box::new(m20220101_000010_create_scenario_table::ScenarioMigration),
+            Box::new(m20220101_000011_add_fork_columns::ForkMigration),
+            Box::new(m20220101_000012_add_draft_event_referee::DraftEventRefereeMigration),
+            Box::new(m20220101_000013_add_draft_team_details::DraftTeamDetailsMigration),
+            Box::new(m20220101_000014_add_draft_settings::DraftSettingsMigration),
+            Box::new(m20220101_000015_add_draft_disabled_champions::DraftDisabledChampionsMigration)
         ]
     }
 }
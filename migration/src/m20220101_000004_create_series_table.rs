@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct SeriesMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for SeriesMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Series::Table)
+                    .if_not_exists()
+                    .col(string(Series::SeriesId))
+                    .primary_key(Index::create().col(Series::SeriesId))
+                    .col(string(Series::Fearless))
+                    .col(date_time(Series::DateCreated))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Draft::Table)
+                    .add_column(string_null(Draft::SeriesId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Draft::Table)
+                    .add_column(integer_null(Draft::GameNumber))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Draft::Table)
+                    .drop_column(Draft::GameNumber)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Draft::Table)
+                    .drop_column(Draft::SeriesId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Series::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Series {
+    Table,
+    SeriesId,
+    Fearless,
+    DateCreated,
+}
+
+#[derive(DeriveIden)]
+enum Draft {
+    Table,
+    SeriesId,
+    GameNumber,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct SeriesGamesMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for SeriesGamesMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Series::Table)
+                    .add_column(integer_null(Series::BestOf))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Series::Table)
+                    .add_column(string(Series::SideSelection).default("Alternate"))
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [Draft::BlueTeam, Draft::RedTeam, Draft::Winner] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .add_column(string_null(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Draft::BlueTeam, Draft::RedTeam, Draft::Winner] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for column in [Series::BestOf, Series::SideSelection] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Series::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Series {
+    Table,
+    BestOf,
+    SideSelection,
+}
+
+#[derive(DeriveIden)]
+enum Draft {
+    Table,
+    BlueTeam,
+    RedTeam,
+    Winner,
+}
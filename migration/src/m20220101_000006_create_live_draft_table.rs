@@ -0,0 +1,36 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct LiveDraftMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for LiveDraftMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LiveDraft::Table)
+                    .if_not_exists()
+                    .col(string(LiveDraft::DraftId))
+                    .primary_key(Index::create().col(LiveDraft::DraftId))
+                    .col(text(LiveDraft::State))
+                    .col(date_time(LiveDraft::UpdatedAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LiveDraft::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LiveDraft {
+    Table,
+    DraftId,
+    State,
+    UpdatedAt,
+}
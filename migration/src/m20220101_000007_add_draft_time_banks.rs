@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct DraftTimeBanksMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for DraftTimeBanksMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Draft::BlueBank, Draft::RedBank] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .add_column(integer_null(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Draft::BlueBank, Draft::RedBank] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Draft {
+    Table,
+    BlueBank,
+    RedBank,
+}
@@ -0,0 +1,35 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct DraftAutoFilledMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for DraftAutoFilledMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Draft::Table)
+                    .add_column(string_null(Draft::AutoFilled))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Draft::Table)
+                    .drop_column(Draft::AutoFilled)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Draft {
+    Table,
+    AutoFilled,
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct DraftEventMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for DraftEventMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DraftEvent::Table)
+                    .if_not_exists()
+                    .col(string(DraftEvent::DraftId))
+                    .col(integer(DraftEvent::Sequence))
+                    .primary_key(Index::create().col(DraftEvent::DraftId).col(DraftEvent::Sequence))
+                    .col(string(DraftEvent::Kind))
+                    .col(string_null(DraftEvent::Side))
+                    .col(integer_null(DraftEvent::Step))
+                    .col(integer_null(DraftEvent::Champion))
+                    .col(date_time(DraftEvent::CreatedAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DraftEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DraftEvent {
+    Table,
+    DraftId,
+    Sequence,
+    Kind,
+    Side,
+    Step,
+    Champion,
+    CreatedAt,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct ScenarioMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for ScenarioMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Scenario::Table)
+                    .if_not_exists()
+                    .col(string(Scenario::ScenarioId))
+                    .primary_key(Index::create().col(Scenario::ScenarioId))
+                    .col(string(Scenario::Name))
+                    .col(string(Scenario::Format))
+                    .col(text(Scenario::State))
+                    .col(date_time(Scenario::DateSaved))
+                    .col(string(Scenario::Owner))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Scenario::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Scenario {
+    Table,
+    ScenarioId,
+    Name,
+    Format,
+    State,
+    DateSaved,
+    Owner,
+}
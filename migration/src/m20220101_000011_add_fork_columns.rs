@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct ForkMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for ForkMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [Draft::Table.into_iden(), Scenario::Table.into_iden()] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table.clone())
+                        .add_column(string_null(Fork::ParentDraftId))
+                        .to_owned(),
+                )
+                .await?;
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(integer_null(Fork::BranchStep))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [Draft::Table.into_iden(), Scenario::Table.into_iden()] {
+            for column in [Fork::ParentDraftId, Fork::BranchStep] {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(table.clone())
+                            .drop_column(column)
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Draft {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum Scenario {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum Fork {
+    ParentDraftId,
+    BranchStep,
+}
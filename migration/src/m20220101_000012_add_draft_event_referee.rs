@@ -0,0 +1,35 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct DraftEventRefereeMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for DraftEventRefereeMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DraftEvent::Table)
+                    .add_column(boolean(DraftEvent::Referee).default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DraftEvent::Table)
+                    .drop_column(DraftEvent::Referee)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DraftEvent {
+    Table,
+    Referee,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct DraftTeamDetailsMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for DraftTeamDetailsMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Draft::BlueTag, Draft::RedTag, Draft::BlueLogo, Draft::RedLogo] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .add_column(string_null(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Draft::BlueTag, Draft::RedTag, Draft::BlueLogo, Draft::RedLogo] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Draft {
+    Table,
+    BlueTag,
+    RedTag,
+    BlueLogo,
+    RedLogo,
+}
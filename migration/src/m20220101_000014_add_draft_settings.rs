@@ -0,0 +1,46 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct DraftSettingsMigration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for DraftSettingsMigration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Draft::Timer, Draft::BanTimeout, Draft::PickTimeout, Draft::HoverVisibility] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .add_column(string_null(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Draft::Timer, Draft::BanTimeout, Draft::PickTimeout, Draft::HoverVisibility] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Draft::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Draft {
+    Table,
+    Timer,
+    BanTimeout,
+    PickTimeout,
+    HoverVisibility,
+}
@@ -104,4 +104,4 @@ pub async fn update_champions() -> Result<Vec<champion::Model>, ServerFnError> {
         .map_err(|db_err| ServerFnError::new(db_err.to_string()))?;
     
     Ok(champion_models)
-}
\ No newline at end of file
+}
@@ -1,13 +1,20 @@
-#[allow(unused_imports)]
-use std::time::Duration;
+use std::str::FromStr;
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 use leptos_meta::{provide_meta_context, Link, MetaTags, Stylesheet, Title};
 use leptos_router::{
-    components::{Route, Router, Routes}, hooks::use_navigate, path
+    components::{Route, Router, Routes}, hooks::{use_navigate, use_query_map}, path
 };
-use thaw::{ConfigProvider, Theme, ToasterProvider};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use thaw::*;
+use uuid::Uuid;
 
-use crate::draft::{completed::CompletedDraft, draft::Draft};
+use crate::draft::{completed::CompletedDraft, draft::{use_error_toast, Draft as DraftPage}, grid::ChampionGrid, sandbox::Sandbox};
+use crate::Draft;
+use crate::error::DraftError;
+use crate::format::{BanTimeout, DraftFormat, FormatPreset, HoverVisibility, PickTimeout, TimeoutPolicy, TurnTimer};
+use crate::series::{FearlessMode, SeriesOptions, SideSelection, TeamInfo};
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
     view! {
@@ -60,9 +67,11 @@ pub fn App() -> impl IntoView {
                     <main>
                         <Routes fallback=|| "Page not found.".into_view()>
                             <Route path=path!("/") view=HomePage/>
-                            <Route path=path!("/draft/:draft_id") view=Draft/>
-                            <Route path=path!("/draft/:draft_id/:team_id") view=Draft/>
+                            <Route path=path!("/draft/:draft_id") view=DraftPage/>
+                            <Route path=path!("/draft/:draft_id/:team_id") view=DraftPage/>
                             <Route path=path!("/completed/:draft_id") view=CompletedDraft/>
+                            <Route path=path!("/sandbox") view=Sandbox/>
+                            <Route path=path!("/sandbox/:scenario_id") view=Sandbox/>
                         </Routes>
                     </main>
                 </Router>
@@ -71,74 +80,378 @@ pub fn App() -> impl IntoView {
     }
 }
 
+/// Everything an organizer chooses on the creation page.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DraftOptions {
+    pub format: FormatPreset,
+    pub timer: TurnTimer,
+    pub timeout: TimeoutPolicy,
+    pub series: SeriesOptions,
+    pub hover_visibility: HoverVisibility,
+    pub disabled_champions: Vec<u32>,
+}
+
+/// The ids behind a new draft's team, spectator, caster and referee links.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DraftLinks {
+    pub draft_id: Uuid,
+    pub blue_id: Uuid,
+    pub red_id: Uuid,
+    pub referee_id: Uuid,
+    pub caster_id: Uuid,
+}
+
+/// The creation page. Query parameters fill in the starting values, so a shared link can preset the options.
 #[component]
 fn HomePage() -> impl IntoView {
-    let navigate = use_navigate();
+    let query = use_query_map();
+    let query_value = |key: &str| query.read_untracked().get(key).unwrap_or_default();
+
+    let format = RwSignal::new(FormatPreset::from_str(&query_value("format")).unwrap_or_default());
 
-    let redirect = OnceResource::new(create_draft());
+    let preset_timer = DraftFormat::from(format.get_untracked()).timer;
+    let seconds = |key: &str, default: Option<i64>| RwSignal::new(
+        query_value(key).parse::<i64>().ok().filter(|seconds| *seconds > 0).or(default).map(|seconds| seconds.to_string()).unwrap_or_default()
+    );
+    let ban_time = seconds("ban_time", Some(preset_timer.ban_seconds));
+    let pick_time = seconds("pick_time", Some(preset_timer.pick_seconds));
+    let ready_time = seconds("ready_time", preset_timer.ready_seconds);
+    let bank_time = seconds("bank_time", Some(preset_timer.bank_seconds));
+    let pauses = RwSignal::new(query_value("pauses").parse().unwrap_or(preset_timer.pauses).to_string());
 
-    Effect::new(move |_| {
-        let Some(redirect) = redirect.get() else { return; };
-        let Ok(redirect) = redirect else { return; };
-        navigate(&redirect, Default::default());
+    // Picking another format starts its timers over from that format's defaults.
+    Effect::new(move |previous: Option<FormatPreset>| {
+        let format = format.get();
+        if previous.is_some_and(|previous| previous != format) {
+            let timer = DraftFormat::from(format).timer;
+            ban_time.set(timer.ban_seconds.to_string());
+            pick_time.set(timer.pick_seconds.to_string());
+            ready_time.set(timer.ready_seconds.map(|seconds| seconds.to_string()).unwrap_or_default());
+            bank_time.set(timer.bank_seconds.to_string());
+            pauses.set(timer.pauses.to_string());
+        }
+        format
     });
 
-    view! {}
+    let ban_timeout = RwSignal::new(BanTimeout::from_str(&query_value("ban_timeout")).unwrap_or_default());
+    let pick_timeout = RwSignal::new(PickTimeout::from_str(&query_value("pick_timeout")).unwrap_or_default());
+
+    let fearless = RwSignal::new(FearlessMode::from_str(&query_value("fearless")).unwrap_or_default());
+    let best_of = RwSignal::new(query_value("best_of"));
+    let side_selection = RwSignal::new(SideSelection::from_str(&query_value("sides")).unwrap_or_default());
+    let team_one = RwSignal::new(query_value("team_one"));
+    let team_one_tag = RwSignal::new(query_value("team_one_tag"));
+    let team_one_logo = RwSignal::new(query_value("team_one_logo"));
+    let team_two = RwSignal::new(query_value("team_two"));
+    let team_two_tag = RwSignal::new(query_value("team_two_tag"));
+    let team_two_logo = RwSignal::new(query_value("team_two_logo"));
+
+    let hover_visibility = RwSignal::new(HoverVisibility::from_str(&query_value("hover_visibility")).unwrap_or_default());
+    let disabled_champions = RwSignal::new(Vec::<u32>::new());
+    let preview = RwSignal::new(Draft::default());
+
+    let options = move || {
+        let number = |value: RwSignal<String>| value.get_untracked().trim().parse::<i64>().ok().filter(|seconds| *seconds >= 0);
+        let team_info = |name: RwSignal<String>, tag: RwSignal<String>, logo: RwSignal<String>| TeamInfo {
+            name: name.get_untracked().trim().to_string(),
+            tag: tag.get_untracked().trim().to_string(),
+            logo: Some(logo.get_untracked().trim().to_string()).filter(|logo| !logo.is_empty()),
+        };
+        let format = format.get_untracked();
+        let preset_timer = DraftFormat::from(format).timer;
+        DraftOptions {
+            format,
+            timer: TurnTimer {
+                ban_seconds: number(ban_time).filter(|seconds| *seconds > 0).unwrap_or(preset_timer.ban_seconds),
+                pick_seconds: number(pick_time).filter(|seconds| *seconds > 0).unwrap_or(preset_timer.pick_seconds),
+                ready_seconds: number(ready_time).filter(|seconds| *seconds > 0),
+                bank_seconds: number(bank_time).unwrap_or(preset_timer.bank_seconds),
+                pauses: number(pauses).map(|pauses| pauses as u32).unwrap_or(preset_timer.pauses),
+                ..preset_timer
+            },
+            timeout: TimeoutPolicy {
+                ban: ban_timeout.get_untracked(),
+                pick: pick_timeout.get_untracked(),
+            },
+            series: SeriesOptions {
+                fearless: fearless.get_untracked(),
+                best_of: best_of.get_untracked().trim().parse().ok(),
+                side_selection: side_selection.get_untracked(),
+                team_one: team_info(team_one, team_one_tag, team_one_logo),
+                team_two: team_info(team_two, team_two_tag, team_two_logo),
+            },
+            hover_visibility: hover_visibility.get_untracked(),
+            disabled_champions: disabled_champions.get_untracked(),
+        }
+    };
+
+    let links = RwSignal::new(None::<DraftLinks>);
+    let creating = RwSignal::new(false);
+    let show_error = use_error_toast();
+    let create = move |_| {
+        let options = options();
+        creating.set(true);
+        spawn_local(async move {
+            match create_draft(options, None).await {
+                Ok(created) => links.set(Some(created)),
+                Err(err) => show_error(Err(err)),
+            }
+            creating.set(false);
+        });
+    };
+
+    view! {
+        <Show
+            when=move || links.get().is_none()
+            fallback=move || view! { <DraftLinksView links=links.get().unwrap()/> }
+        >
+            <Grid cols=4>
+                <GridItem class="max-h-screen overflow-scroll">
+                    <Flex vertical=true justify=FlexJustify::Center class="h-full p-4">
+                        <OptionRow label="Format">{ enum_select(format) }</OptionRow>
+                        <OptionRow label="Team One"><Input value=team_one placeholder="Team 1"/></OptionRow>
+                        <OptionRow label="Team One Tag"><Input value=team_one_tag placeholder="T1"/></OptionRow>
+                        <OptionRow label="Team One Logo"><Input value=team_one_logo placeholder="Image URL"/></OptionRow>
+                        <OptionRow label="Team Two"><Input value=team_two placeholder="Team 2"/></OptionRow>
+                        <OptionRow label="Team Two Tag"><Input value=team_two_tag placeholder="T2"/></OptionRow>
+                        <OptionRow label="Team Two Logo"><Input value=team_two_logo placeholder="Image URL"/></OptionRow>
+                        <OptionRow label="Ban Seconds"><Input value=ban_time/></OptionRow>
+                        <OptionRow label="Pick Seconds"><Input value=pick_time/></OptionRow>
+                        <OptionRow label="Ready Seconds"><Input value=ready_time placeholder="No limit"/></OptionRow>
+                        <OptionRow label="Time Bank Seconds"><Input value=bank_time/></OptionRow>
+                        <OptionRow label="Pauses Per Team"><Input value=pauses/></OptionRow>
+                        <OptionRow label="Ban Timeout">{ enum_select(ban_timeout) }</OptionRow>
+                        <OptionRow label="Pick Timeout">{ enum_select(pick_timeout) }</OptionRow>
+                        <OptionRow label="Hovers">{ enum_select(hover_visibility) }</OptionRow>
+                        <OptionRow label="Fearless">{ enum_select(fearless) }</OptionRow>
+                        <OptionRow label="Best Of"><Input value=best_of placeholder="Open ended"/></OptionRow>
+                        <OptionRow label="Sides">{ enum_select(side_selection) }</OptionRow>
+                        <Button
+                            appearance=ButtonAppearance::Primary
+                            disabled=creating
+                            on:click=create
+                            size=ButtonSize::Large
+                        >"Create Draft"</Button>
+                    </Flex>
+                </GridItem>
+                <GridItem column=3 class="max-h-screen overflow-hidden">
+                    <ChampionGrid
+                        draft=preview
+                        priorities=Signal::derive(move || disabled_champions.get())
+                        on_select=move |champion_id: u32| disabled_champions.update(|disabled| {
+                            match disabled.iter().position(|id| *id == champion_id) {
+                                Some(index) => { disabled.remove(index); }
+                                None => disabled.push(champion_id),
+                            }
+                        })
+                    />
+                    <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            disabled=true
+                            class="!cursor-default"
+                        >{ move || format!("{} Champions Disabled", disabled_champions.get().len()) }</Button>
+                    </Flex>
+                </GridItem>
+            </Grid>
+        </Show>
+    }
+}
+
+#[component]
+fn OptionRow(label: &'static str, children: Children) -> impl IntoView {
+    view! {
+        <Flex justify=FlexJustify::SpaceBetween align=FlexAlign::Center>
+            <span>{label}</span>
+            {children()}
+        </Flex>
+    }
+}
+
+/// A select over every variant of an option enum.
+fn enum_select<T>(value: RwSignal<T>) -> impl IntoView
+where
+    T: IntoEnumIterator + ToString + FromStr + PartialEq + Send + Sync + 'static,
+{
+    view! {
+        <select
+            class="bg-transparent"
+            on:change=move |event| if let Ok(variant) = T::from_str(&event_target_value(&event)) { value.set(variant); }
+        >
+            {
+                T::iter().map(|variant| {
+                    let selected = value.with_untracked(|value| *value == variant);
+                    view! { <option value=variant.to_string() selected=selected>{variant.to_string()}</option> }
+                }).collect_view()
+            }
+        </select>
+    }
+}
+
+/// The links to hand out once a draft is created.
+#[component]
+fn DraftLinksView(links: DraftLinks) -> impl IntoView {
+    let navigate = use_navigate();
+
+    cfg_if::cfg_if! { if #[cfg(feature = "hydrate")] {
+        let origin = window().location().origin().unwrap_or(String::from("http://localhost:3000"));
+    } else {
+        let origin = String::from("http://localhost:3000");
+    }};
+    let spectator_url = format!("{}/draft/{}", origin, links.draft_id);
+    let rows = [
+        ("Blue", format!("{}/{}", spectator_url, links.blue_id)),
+        ("Red", format!("{}/{}", spectator_url, links.red_id)),
+        ("Spectator", spectator_url.clone()),
+        ("Caster", format!("{}/{}", spectator_url, links.caster_id)),
+        ("Referee", format!("{}/{}", spectator_url, links.referee_id)),
+    ];
+
+    view! {
+        <Flex vertical=true justify=FlexJustify::Center align=FlexAlign::Center class="h-screen">
+            {
+                rows.into_iter().map(|(label, url)| {
+                    let copied_url = url.clone();
+                    view! {
+                        <Flex align=FlexAlign::Center>
+                            <b class="w-24">{label}</b>
+                            <span class="w-[40rem] truncate">{url}</span>
+                            <Button
+                                appearance=ButtonAppearance::Secondary
+                                on:click=move |_| { let _ = window().navigator().clipboard().write_text(&copied_url); }
+                            >"Copy"</Button>
+                        </Flex>
+                    }
+                }).collect_view()
+            }
+            <Button
+                appearance=ButtonAppearance::Primary
+                size=ButtonSize::Large
+                on:click=move |_| navigate(&format!("/draft/{}/{}", links.draft_id, links.referee_id), Default::default())
+            >"Open As Referee"</Button>
+        </Flex>
+    }
 }
 
+/// Creates a draft, continuing the series of `previous_draft_id` when given. Otherwise a new series is
+/// started if the options ask for one.
 #[server]
-pub async fn create_draft() -> Result<String, ServerFnError> {
-    use uuid::Uuid;
-    use crate::Draft;
-    use leptos_ws::ServerSignal;
+pub async fn create_draft(options: DraftOptions, previous_draft_id: Option<String>) -> Result<DraftLinks, ServerFnError<DraftError>> {
+    use crate::entity::{draft, series};
+    use crate::series::series_games;
+    use chrono::Local;
+    use sea_orm::*;
+
+    let app_state = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?;
+    let db = app_state.db.clone();
+
+    let DraftOptions { format, timer, timeout, series: options, hover_visibility, disabled_champions } = options;
+    let new_series = || series::Model {
+        series_id: Uuid::new_v4().to_string(),
+        fearless: options.fearless.to_string(),
+        date_created: Local::now().naive_local(),
+        best_of: options.best_of,
+        side_selection: options.side_selection.to_string(),
+    };
 
     let draft_id = Uuid::new_v4();
     let blue_id = Uuid::new_v4();
     let red_id = Uuid::new_v4();
-    let mut draft = Draft::default();
-    draft.draft_id = draft_id.clone();
-    draft.blue_id = blue_id;
-    draft.red_id = red_id;
-
-    let draft_signal = ServerSignal::new(draft_id.to_string(), draft.clone()).unwrap();
-    let draft_timer_signal = ServerSignal::new(format!("{draft_id}timer"), 30).unwrap();
+    let mut format = DraftFormat::from(format);
+    format.timer = timer.checked(format.timer).ok_or(DraftError::InvalidTimer)?;
+    format.timeout = timeout;
+    let referee_id = Uuid::new_v4();
+    let caster_id = Uuid::new_v4();
+    let mut draft = Draft::new(draft_id, blue_id, red_id, format);
+    draft.admin_id = referee_id;
+    draft.caster_id = caster_id;
+    draft.hover_visibility = hover_visibility;
+    draft.disabled_champions = disabled_champions;
 
-    draft_signal.update(move |value| *value = draft);
+    let links = |draft: &Draft| DraftLinks {
+        draft_id: draft.draft_id,
+        blue_id: draft.blue_id,
+        red_id: draft.red_id,
+        referee_id: draft.admin_id,
+        caster_id: draft.caster_id,
+    };
 
-    let mut draft_subscription = draft_signal.subscribe();
-    tokio::spawn(async move {
-        loop {
-            draft_subscription.recv().await.unwrap();
-            let draft = draft_signal.get();
-
-            if draft.blue_ready && draft.red_ready {
-                break;
+    let mut previous_game = None;
+    let series = match previous_draft_id {
+        Some(previous_draft_id) => {
+            let previous = draft::Entity::find_by_id(previous_draft_id).one(&db).await.map_err(DraftError::from)?
+                .ok_or(DraftError::DraftNotFound)?;
+            let previous_draft = Draft::from(previous.clone());
+            previous_game = Some((previous_draft.draft_id, previous.game_number.unwrap_or(1)));
+            draft.blue_team = previous_draft.blue_team;
+            draft.red_team = previous_draft.red_team;
+            match previous.series_id.clone() {
+                Some(series_id) => series::Entity::find_by_id(series_id).one(&db).await.map_err(DraftError::from)?,
+                None => {
+                    let series = series::ActiveModel::from(new_series()).insert(&db).await.map_err(DraftError::from)?;
+                    let mut previous: draft::ActiveModel = previous.into();
+                    previous.series_id = Set(Some(series.series_id.clone()));
+                    previous.game_number = Set(Some(1));
+                    previous.update(&db).await.map_err(DraftError::from)?;
+                    Some(series)
+                }
             }
         }
+        None if !options.is_series() => {
+            draft.blue_team = options.team_one.clone();
+            draft.red_team = options.team_two.clone();
+            None
+        }
+        None => {
+            let team = |team: &TeamInfo, default: &str| match team.name.as_str() {
+                "" => TeamInfo { name: default.to_string(), ..team.clone() },
+                _ => team.clone(),
+            };
+            draft.blue_team = team(&options.team_one, "Team 1");
+            draft.red_team = team(&options.team_two, "Team 2");
+            Some(series::ActiveModel::from(new_series()).insert(&db).await.map_err(DraftError::from)?)
+        }
+    };
 
-        loop {
-            let draft = draft_signal.get();
-            let draft_timer = draft_timer_signal.get();
-
-            if !draft.blue_ready || !draft.red_ready {
-                continue;
-            }
+    if let Some(series) = series {
+        draft.best_of = series.best_of;
+        draft.side_selection = SideSelection::from_str(&series.side_selection).unwrap_or_default();
+        let games = series_games(&db, &series.series_id).await.map_err(DraftError::from)?;
+        let series_id = Uuid::from_str(&series.series_id).map_err(|_| DraftError::DraftNotFound)?;
+        if previous_game.is_some_and(|(_, game_number)| games.iter().any(|game| game.game_number > game_number)) {
+            return Err(DraftError::SeriesMovedOn.into());
+        }
+        draft.join_series(series_id, FearlessMode::from_str(&series.fearless).unwrap_or_default(), games);
 
-            if draft.turn.is_post_draft() {
-                break;
+        // Asking twice for the game after the same one, say from a double click, gets the game already started.
+        if previous_game.is_some() {
+            if let Some(next) = app_state.registry.series_game(&series_id, draft.game_number) {
+                return Ok(links(&next));
             }
+        }
+    }
 
-            if draft_timer < -3 {
-                draft_signal.update(move |value| value.next_turn());
-                draft_timer_signal.update(move |value| *value = 30);
-                continue;
+    // A previous game that's still live could also go on to a game of its own, so it's marked as continued here.
+    if let Some((previous_draft_id, _)) = previous_game {
+        if let Some(previous) = app_state.registry.signal(&previous_draft_id) {
+            let next_draft_id = draft.draft_id;
+            let claimed = previous.try_update(move |value| match value.next_draft_id {
+                Some(next_draft_id) => Err(next_draft_id),
+                None => {
+                    value.next_draft_id = Some(next_draft_id);
+                    Ok(())
+                }
+            });
+            if let Some(Err(next_draft_id)) = claimed {
+                return app_state.registry.signal(&next_draft_id)
+                    .map(|next| links(&next.get()))
+                    .ok_or(DraftError::SeriesMovedOn.into());
             }
-
-            let _ = tokio::time::sleep(Duration::from_secs(1)).await;
-            draft_timer_signal.update(move |value| *value -= 1);
         }
-    });
+    }
+
+    crate::lifecycle::start_draft(draft, &app_state);
 
-    let redirect = format!("/draft/{}", draft_id);
-    Ok(redirect)
+    Ok(DraftLinks { draft_id, blue_id, red_id, referee_id, caster_id })
 }
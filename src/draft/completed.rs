@@ -2,30 +2,115 @@ use chrono::Local;
 use codee::string::JsonSerdeCodec;
 use csv::WriterBuilder;
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 use leptos_router::hooks::{use_navigate, use_params};
 use leptos_router::params::Params;
 use leptos_use::storage::{use_local_storage_with_options, UseStorageOptions};
 use serde::{Deserialize, Serialize};
 use thaw::*;
+use uuid::Uuid;
 use web_sys::js_sys;
-use crate::{entity::draft, Draft, Turn};
+use std::str::FromStr;
+use crate::app::{create_draft, DraftOptions};
+use crate::draft::draft::{get_champions, use_error_toast};
+use crate::draft::replay::ReplayControls;
+use crate::draft::sandbox::use_scenario_owner;
+use crate::draft::side::SideColumn;
+use crate::error::DraftError;
+use crate::event::DraftEvent;
+use crate::format::{BanTimeout, DraftFormat, FormatPreset, HoverVisibility, PickTimeout, Side, MAX_SLOTS};
+use crate::series::{FearlessMode, SeriesOptions, TeamInfo};
+use crate::{entity::{draft, draft_event, scenario, series}, Draft, Turn};
 
-#[server]
-pub async fn save_draft(draft: Draft) -> Result<(), ServerFnError> {
+#[server(CompletedDraft, "/api", "Url", "completed_draft")]
+pub async fn completed_draft(draft_id: String) -> Result<draft::Model, ServerFnError<DraftError>> {
     use sea_orm::*;
-    let db = use_context::<crate::AppState>().ok_or_else(|| ServerFnError::new("Database connection missing."))?.db;
-    let draft: draft::Model = draft.into();
-    let draft: draft::ActiveModel = draft.into();
-    draft.insert(&db).await?;
-    Ok(())
+    let db = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.db;
+    let draft: Option<draft::Model> = draft::Entity::find_by_id(draft_id).one(&db).await.map_err(DraftError::from)?;
+    Ok(draft.ok_or(DraftError::DraftNotFound)?)
 }
 
-#[server(CompletedDraft, "/api", "Url", "completed_draft")]
-pub async fn completed_draft(draft_id: String) -> Result<draft::Model, ServerFnError> {
+#[server(SeriesGames, "/api", "Url", "series_games")]
+pub async fn series_games(series_id: String) -> Result<(series::Model, Vec<draft::Model>), ServerFnError> {
     use sea_orm::*;
     let db = use_context::<crate::AppState>().ok_or_else(|| ServerFnError::new("Database connection missing."))?.db;
-    let draft: Option<draft::Model> = draft::Entity::find_by_id(draft_id).one(&db).await?;
-    draft.ok_or(ServerFnError::new("Draft not found."))
+    let series = series::Entity::find_by_id(series_id.clone()).one(&db).await?
+        .ok_or(ServerFnError::new("Series not found."))?;
+    let games = draft::Entity::find()
+        .filter(draft::Column::SeriesId.eq(series_id))
+        .order_by_asc(draft::Column::GameNumber)
+        .all(&db)
+        .await?;
+    Ok((series, games))
+}
+
+/// Every recorded event of a draft, in the order they happened. Events are stored as a draft runs, but they'd
+/// give away hidden hovers and blind bans, so they're only served once the draft is over.
+#[server(DraftEvents, "/api", "Url", "draft_events")]
+pub async fn draft_events(draft_id: String) -> Result<Vec<draft_event::Model>, ServerFnError<DraftError>> {
+    use sea_orm::*;
+    let app_state = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?;
+    let db = app_state.db;
+    if let Some(live_draft) = Uuid::from_str(&draft_id).ok().and_then(|draft_id| app_state.registry.signal(&draft_id)) {
+        if !live_draft.get().turn.is_post_draft() {
+            return Err(DraftError::DraftInProgress.into());
+        }
+    }
+
+    let events = draft_event::Entity::find()
+        .filter(draft_event::Column::DraftId.eq(draft_id))
+        .order_by_asc(draft_event::Column::Sequence)
+        .all(&db)
+        .await
+        .map_err(DraftError::from)?;
+    Ok(events)
+}
+
+/// Branches a completed draft at `step` into a new sandbox scenario or live draft and returns where to go next.
+#[server(ForkDraft, "/api", "Url", "fork_draft")]
+pub async fn fork_draft(draft_id: String, step: usize, sandbox: bool, owner: Option<String>) -> Result<String, ServerFnError<DraftError>> {
+    use sea_orm::*;
+    let app_state = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?;
+    let parent = draft::Entity::find_by_id(draft_id).one(&app_state.db).await.map_err(DraftError::from)?
+        .ok_or(DraftError::DraftNotFound)?;
+    let parent = Draft::from(parent);
+
+    if sandbox {
+        let mut fork = parent.fork(Uuid::nil(), Uuid::nil(), Uuid::nil(), step);
+        fork.focus(fork.format.turn(step.min(fork.format.steps.len() - 1)));
+        let name = format!("{} vs {} from turn {}", parent.team_name(Side::Blue), parent.team_name(Side::Red), step + 1);
+        let state = serde_json::to_string(&fork).map_err(|_| DraftError::InvalidScenario)?;
+        let saved = crate::draft::sandbox::save_scenario(None, owner, name, state).await?;
+        return Ok(format!("/sandbox/{}", saved.scenario_id));
+    }
+
+    let mut fork = parent.fork(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), step);
+    fork.admin_id = Uuid::new_v4();
+    fork.caster_id = Uuid::new_v4();
+    // Whoever forked the draft runs it, handing out the team links from the referee view.
+    let redirect = format!("/draft/{}/{}", fork.draft_id, fork.admin_id);
+    crate::lifecycle::start_draft(fork, &app_state);
+    Ok(redirect)
+}
+
+/// The completed drafts and saved scenarios that were forked from a draft.
+#[server(DraftForks, "/api", "Url", "draft_forks")]
+pub async fn draft_forks(draft_id: String) -> Result<(Vec<draft::Model>, Vec<scenario::Model>), ServerFnError<DraftError>> {
+    use sea_orm::*;
+    let db = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.db;
+    let drafts = draft::Entity::find()
+        .filter(draft::Column::ParentDraftId.eq(draft_id.clone()))
+        .order_by_asc(draft::Column::BranchStep)
+        .all(&db)
+        .await
+        .map_err(DraftError::from)?;
+    let scenarios = scenario::Entity::find()
+        .filter(scenario::Column::ParentDraftId.eq(draft_id))
+        .order_by_asc(scenario::Column::BranchStep)
+        .all(&db)
+        .await
+        .map_err(DraftError::from)?;
+    Ok((drafts, scenarios))
 }
 
 #[derive(Serialize, Deserialize, Params, PartialEq, Debug)]
@@ -33,7 +118,7 @@ struct CompletedParams {
     draft_id: Option<String>
 }
 
-fn create_csv(draft: draft::Model) -> String {
+pub(crate) fn create_csv(draft: draft::Model) -> String {
     let mut writer = WriterBuilder::new().from_writer(vec![]);
     let _ = writer.serialize(draft);
     let csv = String::from_utf8(writer.into_inner().unwrap_or_default()).unwrap_or_default();
@@ -91,33 +176,190 @@ pub fn CompletedDraft() -> impl IntoView {
         a.click();
     };
 
-    let pick_image = move |turn: Turn| Some(draft.get().get_pick_image(turn));
+    let events_resource = Resource::new(move || draft.get().draft_id, |draft_id| async move {
+        draft_events(draft_id).await.unwrap_or_default()
+    });
+    let events = Signal::derive(move || {
+        events_resource.get().unwrap_or_default().into_iter().filter_map(|event| DraftEvent::try_from(event).ok()).collect::<Vec<_>>()
+    });
+    let champions = Resource::new(|| (), |_| async move { get_champions().await.unwrap_or_default() });
+
+    let replaying = RwSignal::new(false);
+    let replay_position = RwSignal::new(0);
+    Effect::new(move |_| {
+        let _ = draft.get();
+        replaying.set(false);
+    });
+
+    let completed = Signal::derive(move || {
+        let completed = Draft::from(draft.get());
+        if !replaying.get() {
+            return completed;
+        }
+        let events = events.get();
+        completed.replayed(&events[..replay_position.get().min(events.len())])
+    });
+
+    let series_resource = Resource::new(move || draft.get().series_id, |series_id| async move {
+        match series_id {
+            Some(series_id) => series_games(series_id).await.ok(),
+            None => None,
+        }
+    });
+
+    let series_games = Signal::derive(move || series_resource.get().flatten().map(|(_, games)| games).unwrap_or_default());
+    let is_best_of = Signal::derive(move || series_resource.get().flatten().is_some_and(|(series, _)| series.best_of.is_some()));
+
+    let series_score = move || {
+        let games = series_games.get();
+        let Some(first) = games.first().map(|game| Draft::from(game.clone())) else { return String::new(); };
+        let wins = |name: String| games.iter().filter(|game| {
+            let game = Draft::from((*game).clone());
+            game.winner.is_some_and(|winner| game.team_name(winner) == name)
+        }).count();
+        let (blue, red) = (first.team_name(Side::Blue), first.team_name(Side::Red));
+        format!("{} {} - {} {}", blue, wins(blue.clone()), wins(red.clone()), red)
+    };
+
+    let next_redirect = RwSignal::new(None::<String>);
+    let show_error = use_error_toast();
+    let next_game = move |fearless: FearlessMode| {
+        let previous = draft.get_untracked();
+        let format = FormatPreset::from_str(&previous.format).unwrap_or_default();
+        spawn_local(async move {
+            let DraftFormat { timer, timeout, .. } = DraftFormat::from(format);
+            let options = DraftOptions {
+                format,
+                timer,
+                timeout,
+                series: SeriesOptions { fearless, ..Default::default() },
+                ..Default::default()
+            };
+            match create_draft(options, Some(previous.draft_id)).await {
+                Ok(links) => next_redirect.set(Some(format!("/draft/{}/{}", links.draft_id, links.referee_id))),
+                Err(err) => show_error(Err(err)),
+            }
+        });
+    };
+
+    let navigate_next = use_navigate();
+    Effect::new(move |_| {
+        let Some(redirect) = next_redirect.get() else { return; };
+        navigate_next(&redirect, Default::default());
+    });
+
+    let (owner, _) = use_scenario_owner();
+    let fork = move |sandbox: bool| {
+        let (draft_id, step) = (draft.get_untracked().draft_id, completed.get_untracked().step);
+        spawn_local(async move {
+            match fork_draft(draft_id, step, sandbox, owner.get_untracked().map(|owner| owner.to_string())).await {
+                Ok(redirect) => next_redirect.set(Some(redirect)),
+                Err(err) => show_error(Err(err)),
+            }
+        });
+    };
+
+    let forks_resource = Resource::new(move || draft.get().draft_id, |draft_id| async move {
+        draft_forks(draft_id).await.unwrap_or_default()
+    });
+    let forks = Signal::derive(move || {
+        let (drafts, scenarios) = forks_resource.get().unwrap_or_default();
+        let drafts = drafts.into_iter().map(|fork| (
+            format!("/completed/{}", fork.draft_id),
+            format!("Turn {}: Draft {}", fork.branch_step.unwrap_or_default() + 1, fork.date_completed.format("%Y-%m-%d %H:%M")),
+        ));
+        let scenarios = scenarios.into_iter().map(|fork| (
+            format!("/sandbox/{}", fork.scenario_id),
+            format!("Turn {}: {}", fork.branch_step.unwrap_or_default() + 1, fork.name),
+        ));
+        drafts.chain(scenarios).collect::<Vec<_>>()
+    });
 
     view! {
         <Grid cols=5>
             <GridItem><div></div></GridItem>
-            <GridItem class="max-h-screen overflow-hidden flex flex-col items-center blueborders">
-                <video autoplay loop muted class="rotate-180 h-4">
-                    <source src="https://raw.communitydragon.org/pbe/plugins/rcp-fe-lol-static-assets/global/default/videos/long-progress-bar-main-loop.webm" type="video/webm"/>
-                </video>
-                <div class="justify-evenly h-[5%] flex mt-1">
-                    <Image class="w-fit no-drag aspect-square" class:blue=move || draft.get().blue_ban_1.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan1)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:blue=move || draft.get().blue_ban_2.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan2)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:blue=move || draft.get().blue_ban_3.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan3)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:blue=move || draft.get().blue_pick_1.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick1)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:blue=move || draft.get().blue_pick_2.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick2)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:blue=move || draft.get().blue_pick_3.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick3)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <div class="justify-evenly h-[5%] flex">
-                    <Image class="w-fit no-drag aspect-square" class:blue=move || draft.get().blue_ban_4.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan4)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:blue=move || draft.get().blue_ban_5.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan5)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:blue=move || draft.get().blue_pick_4.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick4)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:blue=move || draft.get().blue_pick_5.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick5)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-            </GridItem>
+            <SideColumn side=Side::Blue draft=completed active=true/>
             
             <GridItem column=1 class="max-h-screen overflow-scroll">
                 <Flex vertical=true justify=FlexJustify::Center align=FlexAlign::Center class="h-full">
+                    <Show
+                        when=move || replaying.get()
+                        fallback=|| view! {}
+                    >
+                        <ReplayControls
+                            events=events
+                            position=replay_position
+                            champions=Signal::derive(move || champions.get().unwrap_or_default())
+                        />
+                    </Show>
+                    <Show
+                        when=move || !events.get().is_empty()
+                        fallback=|| view! {}
+                    >
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            class="!cursor-default"
+                            on:click=move |_| {
+                                replay_position.set(0);
+                                replaying.update(|replaying| *replaying = !*replaying);
+                            }
+                        >{ move || if replaying.get() { "Exit Replay" } else { "Replay" } }</Button>
+                    </Show>
+                    <Show
+                        when=move || replaying.get()
+                        fallback=|| view! {}
+                    >
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            class="!cursor-default"
+                            on:click=move |_| fork(true)
+                        >"Fork to Sandbox"</Button>
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            class="!cursor-default"
+                            on:click=move |_| fork(false)
+                        >"Fork Live Draft"</Button>
+                    </Show>
+                    <Show
+                        when=move || draft.get().parent_draft_id.is_some()
+                        fallback=|| view! {}
+                    >
+                        {
+                            let navigate = use_navigate();
+                            view! {
+                                <Button
+                                    appearance=ButtonAppearance::Secondary
+                                    size=ButtonSize::Large
+                                    class="!cursor-default"
+                                    on:click=move |_| {
+                                        let parent_id = draft.get_untracked().parent_draft_id.unwrap_or_default();
+                                        navigate(&format!("/completed/{parent_id}"), Default::default());
+                                    }
+                                >{ move || format!("Forked From Turn {}", draft.get().branch_step.unwrap_or_default() + 1) }</Button>
+                            }
+                        }
+                    </Show>
+                    <For
+                        each=move || forks.get()
+                        key=|(href, _)| href.clone()
+                        let:fork
+                    >
+                        {
+                            let navigate = use_navigate();
+                            let (href, label) = fork;
+                            view! {
+                                <Button
+                                    appearance=ButtonAppearance::Secondary
+                                    size=ButtonSize::Large
+                                    class="!cursor-default"
+                                    on:click=move |_| navigate(&href, Default::default())
+                                >{ label }</Button>
+                            }
+                        }
+                    </For>
                     <Button
                         appearance=ButtonAppearance::Secondary
                         size=ButtonSize::Large
@@ -130,6 +372,75 @@ pub fn CompletedDraft() -> impl IntoView {
                         class="!cursor-default"
                         on:click=move |_| navigate("/", Default::default())
                     >"New Draft"</Button>
+                    <Show
+                        when=move || !series_games.get().is_empty()
+                        fallback=|| view! {}
+                    >
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            class="!cursor-default"
+                            disabled=true
+                        >{ series_score }</Button>
+                        <For
+                            each=move || series_games.get()
+                            key=|game| game.draft_id.clone()
+                            let:game
+                        >
+                            {
+                                let navigate = use_navigate();
+                                let game_id = game.draft_id.clone();
+                                let game = Draft::from(game);
+                                let label = format!(
+                                    "Game {}: {} vs {}{}",
+                                    game.game_number,
+                                    game.team_name(Side::Blue),
+                                    game.team_name(Side::Red),
+                                    game.winner.map(|winner| format!(" ({} won)", game.team_name(winner))).unwrap_or_default(),
+                                );
+                                let is_current = {
+                                    let game_id = game_id.clone();
+                                    move || game_id == draft.get().draft_id
+                                };
+                                view! {
+                                    <Button
+                                        appearance=Signal::derive(move || if is_current() { ButtonAppearance::Primary } else { ButtonAppearance::Secondary })
+                                        size=ButtonSize::Large
+                                        class="!cursor-default"
+                                        on:click=move |_| navigate(&format!("/completed/{}", game_id), Default::default())
+                                    >{ label }</Button>
+                                }
+                            }
+                        </For>
+                    </Show>
+                    <Show
+                        when=move || draft.get().series_id.is_none()
+                        fallback=|| view! {}
+                    >
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            class="!cursor-default"
+                            on:click=move |_| next_game(FearlessMode::Hard)
+                        >"Fearless Next Game"</Button>
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            class="!cursor-default"
+                            on:click=move |_| next_game(FearlessMode::Soft)
+                        >"Soft Fearless Next Game"</Button>
+                    </Show>
+                    <Show
+                        when=move || draft.get().series_id.is_some() && !is_best_of.get()
+                        fallback=|| view! {}
+                    >
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            class="!cursor-default"
+                            on:click=move |_| next_game(FearlessMode::default())
+                        >{ move || format!("Next Game ({})", draft.get().game_number.unwrap_or(1) + 1) }</Button>
+                    </Show>
                     <Show
                         when=move || {
                             let draft_id = draft.get().draft_id;
@@ -167,25 +478,7 @@ pub fn CompletedDraft() -> impl IntoView {
                 </Flex>
             </GridItem>
             
-            <GridItem class="max-h-screen overflow-hidden flex flex-col items-center redborders">
-                <video autoplay loop muted class="red h-4">
-                    <source src="https://raw.communitydragon.org/pbe/plugins/rcp-fe-lol-static-assets/global/default/videos/long-progress-bar-main-loop.webm" type="video/webm"/>
-                </video>
-                <div class="justify-evenly h-[5%] flex mt-1">
-                    <Image class="w-fit no-drag aspect-square" src=MaybeProp::derive(move || pick_image(Turn::RedBan1)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" src=MaybeProp::derive(move || pick_image(Turn::RedBan2)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" src=MaybeProp::derive(move || pick_image(Turn::RedBan3)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" src=MaybeProp::derive(move || pick_image(Turn::RedPick1)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" src=MaybeProp::derive(move || pick_image(Turn::RedPick2)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" src=MaybeProp::derive(move || pick_image(Turn::RedPick3)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <div class="justify-evenly h-[5%] flex">
-                    <Image class="w-fit no-drag aspect-square" src=MaybeProp::derive(move || pick_image(Turn::RedBan4)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" src=MaybeProp::derive(move || pick_image(Turn::RedBan5)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" src=MaybeProp::derive(move || pick_image(Turn::RedPick4)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" src=MaybeProp::derive(move || pick_image(Turn::RedPick5)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-            </GridItem>
+            <SideColumn side=Side::Red draft=completed active=true/>
             <GridItem><div></div></GridItem>
         </Grid>
     }
@@ -193,72 +486,101 @@ pub fn CompletedDraft() -> impl IntoView {
 
 impl From<Draft> for draft::Model {
     fn from(value: Draft) -> Self {
+        let slot = |slots: &Vec<Option<u32>>, index: usize| slots.get(index).copied().flatten();
+        let has_bank = value.has_bank();
         draft::Model {
             draft_id: value.draft_id.to_string(),
-            blue_ban_1: value.blue_ban_1,
-            blue_ban_2: value.blue_ban_2,
-            blue_ban_3: value.blue_ban_3,
-            blue_ban_4: value.blue_ban_4,
-            blue_ban_5: value.blue_ban_5,
-            red_ban_1: value.red_ban_1,
-            red_ban_2: value.red_ban_2,
-            red_ban_3: value.red_ban_3,
-            red_ban_4: value.red_ban_4,
-            red_ban_5: value.red_ban_5,
-            blue_pick_1: value.blue_pick_1,
-            blue_pick_2: value.blue_pick_2,
-            blue_pick_3: value.blue_pick_3,
-            blue_pick_4: value.blue_pick_4,
-            blue_pick_5: value.blue_pick_5,
-            red_pick_1: value.red_pick_1,
-            red_pick_2: value.red_pick_2,
-            red_pick_3: value.red_pick_3,
-            red_pick_4: value.red_pick_4,
-            red_pick_5: value.red_pick_5,
-            date_completed: Local::now().naive_local()
+            format: value.format.preset.to_string(),
+            blue_ban_1: slot(&value.blue_bans, 0),
+            blue_ban_2: slot(&value.blue_bans, 1),
+            blue_ban_3: slot(&value.blue_bans, 2),
+            blue_ban_4: slot(&value.blue_bans, 3),
+            blue_ban_5: slot(&value.blue_bans, 4),
+            red_ban_1: slot(&value.red_bans, 0),
+            red_ban_2: slot(&value.red_bans, 1),
+            red_ban_3: slot(&value.red_bans, 2),
+            red_ban_4: slot(&value.red_bans, 3),
+            red_ban_5: slot(&value.red_bans, 4),
+            blue_pick_1: slot(&value.blue_picks, 0),
+            blue_pick_2: slot(&value.blue_picks, 1),
+            blue_pick_3: slot(&value.blue_picks, 2),
+            blue_pick_4: slot(&value.blue_picks, 3),
+            blue_pick_5: slot(&value.blue_picks, 4),
+            red_pick_1: slot(&value.red_picks, 0),
+            red_pick_2: slot(&value.red_picks, 1),
+            red_pick_3: slot(&value.red_picks, 2),
+            red_pick_4: slot(&value.red_picks, 3),
+            red_pick_5: slot(&value.red_picks, 4),
+            date_completed: Local::now().naive_local(),
+            series_id: value.series_id.map(|series_id| series_id.to_string()),
+            game_number: value.series_id.map(|_| value.game_number),
+            blue_team: Some(value.blue_team.name).filter(|name| !name.is_empty()),
+            red_team: Some(value.red_team.name).filter(|name| !name.is_empty()),
+            blue_tag: Some(value.blue_team.tag).filter(|tag| !tag.is_empty()),
+            red_tag: Some(value.red_team.tag).filter(|tag| !tag.is_empty()),
+            blue_logo: value.blue_team.logo,
+            red_logo: value.red_team.logo,
+            winner: value.winner.map(|winner| winner.to_string()),
+            blue_bank: has_bank.then(|| (value.blue_bank_millis / 1000) as i32),
+            red_bank: has_bank.then(|| (value.red_bank_millis / 1000) as i32),
+            auto_filled: Some(value.auto_filled.iter().map(|step| step.to_string()).collect::<Vec<_>>().join(","))
+                .filter(|auto_filled| !auto_filled.is_empty()),
+            parent_draft_id: value.parent_id.map(|parent_id| parent_id.to_string()),
+            branch_step: value.branch_step.map(|step| step as u32),
+            timer: serde_json::to_string(&value.format.timer).ok(),
+            ban_timeout: Some(value.format.timeout.ban.to_string()),
+            pick_timeout: Some(value.format.timeout.pick.to_string()),
+            hover_visibility: Some(value.hover_visibility.to_string()),
+            disabled_champions: Some(value.disabled_champions.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","))
+                .filter(|disabled_champions| !disabled_champions.is_empty()),
         }
     }
 }
 
-impl draft::Model {
-    fn get_pick_image(&self, turn: Turn) -> String {
-        if let Some(pick_id) = self.get_pick(&turn) {
-            if turn.is_ban() {
-                return format!("https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/champion-icons/{}.png", pick_id)
-            } else {
-                return format!("https://cdn.communitydragon.org/latest/champion/{}/splash-art/centered/skin/0.jpg", pick_id);
-            }
+impl From<draft::Model> for Draft {
+    fn from(value: draft::Model) -> Self {
+        let mut format: DraftFormat = FormatPreset::from_str(&value.format).unwrap_or_default().into();
+        if let Some(timer) = value.timer.as_deref().and_then(|timer| serde_json::from_str(timer).ok()) {
+            format.timer = timer;
         }
-        String::from("https://raw.communitydragon.org/latest/game/assets/ux/loadingscreen/srworlds2023loadscreen.png")
-    }
-    
-    fn get_pick(&self, turn: &Turn) -> Option<u32> {
-        match turn {
-            Turn::PreDraft => None,
-            Turn::BlueBan1 => self.blue_ban_1,
-            Turn::BlueBan2 => self.blue_ban_2,
-            Turn::BlueBan3 => self.blue_ban_3,
-            Turn::BlueBan4 => self.blue_ban_4,
-            Turn::BlueBan5 => self.blue_ban_5,
-            
-            Turn::RedBan1 => self.red_ban_1,
-            Turn::RedBan2 => self.red_ban_2,
-            Turn::RedBan3 => self.red_ban_3,
-            Turn::RedBan4 => self.red_ban_4,
-            Turn::RedBan5 => self.red_ban_5,
-    
-            Turn::BluePick1 => self.blue_pick_1,
-            Turn::BluePick2 => self.blue_pick_2,
-            Turn::BluePick3 => self.blue_pick_3,
-            Turn::BluePick4 => self.blue_pick_4,
-            Turn::BluePick5 => self.blue_pick_5,
-    
-            Turn::RedPick1 => self.red_pick_1,
-            Turn::RedPick2 => self.red_pick_2,
-            Turn::RedPick3 => self.red_pick_3,
-            Turn::RedPick4 => self.red_pick_4,
-            Turn::RedPick5 => self.red_pick_5,
-            Turn::PostDraft => None
+        if let Some(ban) = value.ban_timeout.as_deref().and_then(|ban| BanTimeout::from_str(ban).ok()) {
+            format.timeout.ban = ban;
+        }
+        if let Some(pick) = value.pick_timeout.as_deref().and_then(|pick| PickTimeout::from_str(pick).ok()) {
+            format.timeout.pick = pick;
+        }
+        let bans = |slots: [Option<u32>; MAX_SLOTS]| slots.into_iter().take(format.ban_count).collect();
+        let picks = |slots: [Option<u32>; MAX_SLOTS]| slots.into_iter().take(format.pick_count).collect();
+        Draft {
+            draft_id: Uuid::from_str(&value.draft_id).unwrap_or_default(),
+            blue_bans: bans([value.blue_ban_1, value.blue_ban_2, value.blue_ban_3, value.blue_ban_4, value.blue_ban_5]),
+            red_bans: bans([value.red_ban_1, value.red_ban_2, value.red_ban_3, value.red_ban_4, value.red_ban_5]),
+            blue_picks: picks([value.blue_pick_1, value.blue_pick_2, value.blue_pick_3, value.blue_pick_4, value.blue_pick_5]),
+            red_picks: picks([value.red_pick_1, value.red_pick_2, value.red_pick_3, value.red_pick_4, value.red_pick_5]),
+            step: format.steps.len(),
+            turn: Turn::PostDraft,
+            format,
+            series_id: value.series_id.and_then(|series_id| Uuid::from_str(&series_id).ok()),
+            game_number: value.game_number.unwrap_or(1),
+            blue_team: TeamInfo {
+                name: value.blue_team.unwrap_or_default(),
+                tag: value.blue_tag.unwrap_or_default(),
+                logo: value.blue_logo,
+            },
+            red_team: TeamInfo {
+                name: value.red_team.unwrap_or_default(),
+                tag: value.red_tag.unwrap_or_default(),
+                logo: value.red_logo,
+            },
+            winner: value.winner.and_then(|winner| Side::from_str(&winner).ok()),
+            blue_bank_millis: value.blue_bank.unwrap_or_default() as i64 * 1000,
+            red_bank_millis: value.red_bank.unwrap_or_default() as i64 * 1000,
+            auto_filled: value.auto_filled.unwrap_or_default().split(',').filter_map(|step| step.parse().ok()).collect(),
+            parent_id: value.parent_draft_id.and_then(|parent_id| Uuid::from_str(&parent_id).ok()),
+            branch_step: value.branch_step.map(|step| step as usize),
+            hover_visibility: value.hover_visibility.and_then(|visibility| HoverVisibility::from_str(&visibility).ok()).unwrap_or_default(),
+            disabled_champions: value.disabled_champions.unwrap_or_default().split(',').filter_map(|id| id.parse().ok()).collect(),
+            ..Default::default()
         }
     }
 }
@@ -1,6 +1,5 @@
-use codee::string::JsonSerdeCodec;
-use csv::WriterBuilder;
-use gloo_timers::callback::Timeout;
+use chrono::{DateTime, TimeDelta, Utc};
+use codee::string::{FromToStringCodec, JsonSerdeCodec};
 use leptos::prelude::*;
 use leptos::Params;
 use leptos_router::hooks::use_navigate;
@@ -13,14 +12,18 @@ use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 use strum_macros::EnumIs;
 use thaw::*;
-use strum::IntoEnumIterator;
 use uuid::Uuid;
 use web_sys::js_sys;
-use std::{str::FromStr, string::ToString};
-use crate::draft::completed::save_draft;
+use std::{str::FromStr, string::ToString, time::Duration};
+use crate::draft::completed::create_csv;
+use crate::draft::grid::ChampionGrid;
+use crate::draft::side::SideColumn;
+use crate::format::Side;
+use crate::series::TeamInfo;
 use crate::entity::champion;
-use crate::api::Role;
-use crate::{Draft, Turn};
+use crate::error::DraftError;
+use crate::event::EventKind;
+use crate::{Draft, SeatPass, Turn};
 
 #[derive(Serialize, Deserialize, Params, PartialEq, Debug)]
 struct DraftParams {
@@ -32,9 +35,31 @@ struct DraftParams {
 enum Team {
     Blue,
     Red,
+    Referee,
     Spectator,
 }
 
+/// An override made from the referee link.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefereeAction {
+    Ready(Side),
+    /// Locks in the acting team's hover, or an empty slot if there isn't one.
+    Advance,
+    /// Takes back the last locked turn.
+    Revert,
+    SetSlot(Turn, Option<u32>),
+    Pause,
+    Resume,
+    Reset,
+    Cancel,
+    /// Unseats a team's players so another browser can claim the seat.
+    Release(Side),
+    /// Replaces a team's link, leaving the old one as a spectator link.
+    Relink(Side),
+    /// Sets or corrects the game's result in a series, until its next game is started.
+    SetWinner(Side),
+}
+
 #[component]
 pub fn Draft() -> impl IntoView {
     let params = use_params::<DraftParams>();
@@ -77,27 +102,30 @@ pub fn Draft() -> impl IntoView {
         navigate("/", Default::default());
     });
 
-    let selected_role = RwSignal::new(Role::default().to_string());
-    let delay = RwSignal::new(false);
-    let search = RwSignal::new(String::new());
-    
-    let server_draft = ServerSignal::new(draft_id(), Draft::default()).unwrap();
+    // Each link gets its own filtered view of the draft, so a team never receives what the policy hides from it.
+    let view_name = match team_id() {
+        Some(team_id) => format!("{}/{}", draft_id(), team_id),
+        None => draft_id(),
+    };
+    let server_draft = ServerSignal::new(view_name, Draft::default()).unwrap();
     let draft = RwSignal::new(Draft::default());
 
-    let server_draft_timer = ServerSignal::new(format!("{}timer", draft_id()), 30).unwrap();
-    let draft_timer = RwSignal::new(30);
+    let now = RwSignal::new(Utc::now());
+    let draft_timer = Signal::derive(move || draft.get().seconds_left(now.get()));
+    let ready_countdown = move || draft.get().ready_seconds_left(now.get()).map(|seconds| format!(" ({seconds})")).unwrap_or_default();
+    let bank_countdown = move |side: Side| move || {
+        let draft = draft.get();
+        if draft.has_bank() { format!("+{}", draft.bank_seconds_left(side, now.get())) } else { String::new() }
+    };
 
     let navigate = use_navigate();
-    let done = RwSignal::new(false);
-    Effect::new(move |_| {
-        let draft = draft.get();
-        if draft.turn.is_post_draft() {
-            spawn_local(async move {
-                let _ = save_draft(draft).await;
-                done.set(true);
-            });
-        }
-    });
+    let show_error = use_error_toast();
+
+    // Sent with every command, so one made against a state the server has moved past is rejected.
+    let version = move || draft.get_untracked().version;
+    // Names the step a hover or lock is for, so one sent twice or late can't land on the team's next step of a blind phase.
+    let acting_step = move || side().and_then(|side| draft.get_untracked().side_step(side)).unwrap_or_default();
+    let done = Memo::new(move |_| draft.get().saved);
 
     let (_, drafts_set, _) = use_local_storage::<Vec<crate::entity::draft::Model>, JsonSerdeCodec>("Drafts");
     Effect::new(move |_| {
@@ -105,20 +133,51 @@ pub fn Draft() -> impl IntoView {
             drafts_set.update(|drafts| {
                 let draft: crate::entity::draft::Model = draft.get_untracked().into();
                 
-                drafts.push(draft);
+                if !drafts.iter().any(|saved| saved.draft_id == draft.draft_id) {
+                    drafts.push(draft);
+                }
             });
-            navigate(&format!("/completed/{}", draft_id()), Default::default());
         }
     });
 
     Effect::new(move |_| {
-        draft_timer.set(server_draft_timer.get());
+        if !done.get() {
+            return;
+        }
+
+        let draft = draft.get();
+        match draft.next_draft_id {
+            // A full page load so the draft page reconnects to the next game's signals.
+            Some(next_draft_id) => {
+                let next_url = match team_id() {
+                    Some(team_id) => format!("/draft/{next_draft_id}/{team_id}"),
+                    None => format!("/draft/{next_draft_id}"),
+                };
+                let _ = window().location().set_href(&next_url);
+            }
+            None if draft.series_in_progress() => (),
+            None => navigate(&format!("/completed/{}", draft_id()), Default::default()),
+        }
     });
 
+    // Deadlines are set by the server's clock, so the countdowns run on it instead of this device's, taking the
+    // server's time as of halfway through the round trip.
+    let clock_offset = RwSignal::new(TimeDelta::zero());
     Effect::new(move |_| {
-        draft.set(server_draft.get());
+        spawn_local(async move {
+            let sent = Utc::now();
+            if let Ok(server_now) = server_time().await {
+                let received = Utc::now();
+                clock_offset.set(server_now - (sent + (received - sent) / 2));
+            }
+        });
+        if let Ok(handle) = set_interval_with_handle(move || now.set(Utc::now() + clock_offset.get_untracked()), Duration::from_millis(250)) {
+            on_cleanup(move || handle.clear());
+        }
     });
 
+    Effect::new(move |_| draft.set(server_draft.get()));
+
     cfg_if::cfg_if! { if #[cfg(feature = "hydrate")] {
         let origin = window().location().origin().unwrap_or(String::from("http://localhost:3000"));
     } else {
@@ -127,25 +186,9 @@ pub fn Draft() -> impl IntoView {
     let spectator_url = Signal::derive(move || format!("{}/draft/{}", origin.clone(), draft.get().draft_id));
     let blue_url = Signal::derive(move || format!("{}/{}", spectator_url.get(), draft.get().blue_id));
     let red_url = Signal::derive(move || format!("{}/{}", spectator_url.get(), draft.get().red_id));
+    let referee_url = Signal::derive(move || format!("{}/{}", spectator_url.get(), draft.get().admin_id));
+    let caster_url = Signal::derive(move || format!("{}/{}", spectator_url.get(), draft.get().caster_id));
     
-    let champions = Resource::new(|| (), move |_| async move {
-        if let Ok(champs) = get_champions().await {
-            champs
-        } else {
-            vec![]
-        }
-    });
-
-    Effect::new(move |_| {
-        let _ = champions.get();
-        let timeout = Timeout::new(1_000, move || {
-            delay.set(true);
-        });
-        timeout.forget();
-    });
-
-    let pick_image = move |turn: Turn| Some(draft.get().get_pick_image(turn));
-
     let team = move || {
         let team_id = match team_id() {
             Some(team_id) => team_id,
@@ -160,6 +203,10 @@ pub fn Draft() -> impl IntoView {
             }
         };
 
+        if draft.get().is_admin(&team_id) {
+            return Team::Referee;
+        }
+
         let blue = team_id == draft.get().blue_id;
         let red = team_id == draft.get().red_id;
 
@@ -171,42 +218,455 @@ pub fn Draft() -> impl IntoView {
         }
     };
 
-    let link_copied = RwSignal::new(false);
+    let is_turn = move || {
+        team().is_blue() && draft.get().is_acting(Side::Blue) ||
+        team().is_red() && draft.get().is_acting(Side::Red)
+    };
+
+    let is_ready = move || {
+        team().is_blue() && draft.get().blue_ready ||
+        team().is_red() && draft.get().red_ready
+    };
+
+    let side = move || match team() {
+        Team::Blue => Some(Side::Blue),
+        Team::Red => Some(Side::Red),
+        Team::Referee | Team::Spectator => None,
+    };
+
+    // The seat this browser holds under the team link, kept per draft and team so one browser can hold seats in several.
+    let seat_pass_key = Signal::derive(move || format!("SeatPass/{}/{}", draft_id(), team_id().unwrap_or_default()));
+    let (seat_pass, set_seat_pass, _) = use_local_storage::<Option<SeatPass>, JsonSerdeCodec>(seat_pass_key);
+    let (player_name, set_player_name, _) = use_local_storage::<String, FromToStringCodec>("PlayerName");
+    let token = move || seat_pass.get_untracked().map(|pass| pass.token.to_string());
+    let player = move || side().and_then(|side| {
+        let player_id = seat_pass.get()?.player_id;
+        draft.get().players(side).iter().find(|player| player.player_id == player_id).cloned()
+    });
+    let can_act = move || side().is_some_and(|side| draft.get().can_player_act(side, player().as_ref()));
+
+    let join = move |name: String| spawn_local(async move {
+        match join_team(draft_id(), team_id().unwrap_or_default(), token(), name.clone()).await {
+            Ok(pass) => {
+                set_seat_pass.set(Some(pass));
+                set_player_name.set(name);
+            }
+            Err(err) => show_error(Err(err)),
+        }
+    });
+
+    let auto_joined = RwSignal::new(false);
     Effect::new(move |_| {
-        if draft.get().draft_id.to_string() == "00000000-0000-0000-0000-000000000000" ||
-            !draft.get().turn.is_pre_draft() ||
-            !team().is_spectator() ||
-            link_copied.get()
-        {
+        if auto_joined.get() || draft.get().draft_id.is_nil() || side().is_none() || player_name.get().is_empty() {
             return;
         }
+        auto_joined.set(true);
+        if player().is_none() {
+            join(player_name.get_untracked());
+        }
+    });
 
-        cfg_if::cfg_if! { if #[cfg(feature = "hydrate")] {
-            let _ = window().navigator().clipboard().write_text(&format!("Blue: {}\nSpectator: {}\nRed: {}", blue_url.get(), spectator_url.get(), red_url.get()));
-    
-            let toaster = ToasterInjection::expect_context();
-            toaster.dispatch_toast(move || view! {
-                <Toast>
-                    <ToastTitle>"Links Copied To Clipboard"</ToastTitle>
-                </Toast>
-            }, ToastOptions::default().with_position(ToastPosition::Bottom));
-
-            link_copied.set(true);
-        }}
+    let new_name = RwSignal::new(player_name.get_untracked());
+    let player_controls = move || {
+        let Some(side) = side() else { return view! {}.into_any(); };
+        let Some(current) = player() else {
+            return view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Input value=new_name placeholder="Your Name"/>
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        disabled=Signal::derive(move || new_name.get().trim().is_empty())
+                        on:click=move |_| join(new_name.get_untracked().trim().to_string())
+                    >"Join Team"</Button>
+                </Flex>
+            }.into_any();
+        };
+
+        let teammates = draft.get().players(side).clone();
+        let has_suggestion = current.suggestion.is_some();
+        view! {
+            <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                {
+                    teammates.into_iter().map(|teammate| {
+                        let label = format!("{}{}", teammate.name, if teammate.captain { " (C)" } else { "" });
+                        let promotable = current.captain && !teammate.captain;
+                        let captain_id = teammate.player_id.to_string();
+                        view! {
+                            <Button
+                                appearance=if teammate.player_id == current.player_id { ButtonAppearance::Primary } else { ButtonAppearance::Secondary }
+                                class=if promotable { "" } else { "!cursor-default" }
+                                on:click=move |_| {
+                                    if !promotable { return; }
+                                    let captain_id = captain_id.clone();
+                                    spawn_local(async move {
+                                        show_error(make_captain(draft_id(), team_id().unwrap_or_default(), token(), captain_id).await);
+                                    });
+                                }
+                            >{ label }</Button>
+                        }
+                    }).collect_view()
+                }
+                <Show
+                    when=move || has_suggestion
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| spawn_local(async move {
+                            show_error(suggest(draft_id(), team_id().unwrap_or_default(), token(), None).await);
+                        })
+                    >"Clear Suggestion"</Button>
+                </Show>
+            </Flex>
+        }.into_any()
+    };
+
+    let series_score = move || {
+        let draft = draft.get();
+        format!(
+            "{} {} - {} {}",
+            draft.team_name(Side::Blue),
+            draft.series_wins(Side::Blue),
+            draft.series_wins(Side::Red),
+            draft.team_name(Side::Red),
+        )
+    };
+
+    let series_controls = move || {
+        let draft = draft.get();
+        let loser = draft.winner.map(|winner| winner.opponent());
+        match (draft.winner, side()) {
+            // Once a team has reported, it waits on the other to agree, or on the referee if they don't.
+            (None, Some(side)) if draft.report(side).is_some() => {
+                let status = match draft.report(side.opponent()) {
+                    Some(report) if draft.report(side) != Some(report) => String::from("Results disagree, waiting on the referee"),
+                    _ => format!("Waiting on {} to confirm", draft.team_name(side.opponent())),
+                };
+                view! {
+                    <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            disabled=true
+                            class="!cursor-default"
+                        >{ status }</Button>
+                    </Flex>
+                }.into_any()
+            },
+            (None, Some(_)) => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(report_winner(draft_id(), team_id().unwrap_or_default(), token(), version(), Side::Blue).await); })
+                        size=ButtonSize::Large
+                    >{ format!("{} Won", draft.team_name(Side::Blue)) }</Button>
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(report_winner(draft_id(), team_id().unwrap_or_default(), token(), version(), Side::Red).await); })
+                        size=ButtonSize::Large
+                    >{ format!("{} Won", draft.team_name(Side::Red)) }</Button>
+                </Flex>
+            }.into_any(),
+            (Some(_), Some(_)) if draft.side_selection.is_alternate() && draft.series_in_progress() && draft.next_draft_id.is_none() => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(next_game(draft_id(), team_id().unwrap_or_default(), token(), version()).await); })
+                        size=ButtonSize::Large
+                    >{ format!("{} - Start Next Game", series_score()) }</Button>
+                </Flex>
+            }.into_any(),
+            (Some(_), Some(side)) if draft.side_selection.is_loser_picks() && loser == Some(side) => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(choose_side(draft_id(), team_id().unwrap_or_default(), token(), version(), Side::Blue).await); })
+                        size=ButtonSize::Large
+                    >"Play Blue Side"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(choose_side(draft_id(), team_id().unwrap_or_default(), token(), version(), Side::Red).await); })
+                        size=ButtonSize::Large
+                    >"Play Red Side"</Button>
+                </Flex>
+            }.into_any(),
+            (_, _) => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        size=ButtonSize::Large
+                        disabled=true
+                        class="!cursor-default"
+                    >{ series_score }</Button>
+                </Flex>
+            }.into_any(),
+        }
+    };
+
+    let team_name = RwSignal::new(String::new());
+    let team_tag = RwSignal::new(String::new());
+    let team_logo = RwSignal::new(String::new());
+    Effect::new(move |previous: Option<Option<TeamInfo>>| {
+        let team = side().map(|side| draft.get().team(side).clone());
+        if previous.as_ref() != Some(&team) {
+            let team = team.clone().unwrap_or_default();
+            team_name.set(team.name);
+            team_tag.set(team.tag);
+            team_logo.set(team.logo.unwrap_or_default());
+        }
+        team
     });
 
-    let is_turn = move || {
-        team().is_blue() && draft.get().turn.is_blue() ||
-        team().is_red() && draft.get().turn.is_red()
+    let team_controls = move || {
+        let draft = draft.get();
+        let editable = side().is_some_and(|side| draft.turn.is_pre_draft() && !match side {
+            Side::Blue => draft.blue_ready,
+            Side::Red => draft.red_ready,
+        });
+        if !editable {
+            return view! {}.into_any();
+        }
+        view! {
+            <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                <Input value=team_name placeholder="Team Name"/>
+                <Input value=team_tag placeholder="Tag"/>
+                <Input value=team_logo placeholder="Logo URL"/>
+                <Button
+                    appearance=ButtonAppearance::Secondary
+                    on:click=move |_| {
+                        let team = TeamInfo {
+                            name: team_name.get_untracked(),
+                            tag: team_tag.get_untracked(),
+                            logo: Some(team_logo.get_untracked()),
+                        };
+                        spawn_local(async move { show_error(set_team(draft_id(), team_id().unwrap_or_default(), token(), team).await); });
+                    }
+                >"Save Team"</Button>
+            </Flex>
+        }.into_any()
     };
 
-    let is_ready = move || {
-        team().is_blue() && draft.get().blue_ready ||
-        team().is_red() && draft.get().red_ready
+    // The slot a referee picked to fill with the next champion they click.
+    let referee_slot = RwSignal::new(None::<Turn>);
+    let referee_action = move |action: RefereeAction| spawn_local(async move {
+        show_error(referee(draft_id(), team_id().unwrap_or_default(), version(), action).await);
+    });
+
+    let referee_controls = move || {
+        if !team().is_referee() {
+            return view! {}.into_any();
+        }
+        let draft = draft.get();
+        let (pre_draft, in_turn, post_draft) = (draft.turn.is_pre_draft(), draft.turn.side().is_some(), draft.turn.is_post_draft());
+        let (paused, can_revert, pending_undo) = (draft.paused.is_some(), draft.undoable_step().is_some(), draft.pending_undo(now.get()).is_some());
+        let can_set_winner = post_draft && draft.best_of.is_some() && draft.next_draft_id.is_none();
+        let can_start_next = can_set_winner && draft.winner.is_some() && draft.side_selection.is_alternate() && draft.series_in_progress();
+        view! {
+            <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                <Show
+                    when=move || pre_draft
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        disabled=draft.blue_ready
+                        on:click=move |_| referee_action(RefereeAction::Ready(Side::Blue))
+                    >"Ready Blue"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        disabled=draft.red_ready
+                        on:click=move |_| referee_action(RefereeAction::Ready(Side::Red))
+                    >"Ready Red"</Button>
+                </Show>
+                <Show
+                    when=move || in_turn
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::Advance)
+                    >"Advance"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        disabled=!can_revert
+                        on:click=move |_| referee_action(RefereeAction::Revert)
+                    >"Revert"</Button>
+                </Show>
+                <Show
+                    when=move || pending_undo
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(approve_undo(draft_id(), team_id().unwrap_or_default(), token(), version()).await); })
+                    >"Approve Undo"</Button>
+                </Show>
+                <Show
+                    when=move || referee_slot.get().is_some()
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| {
+                            let Some(turn) = referee_slot.get_untracked() else { return; };
+                            referee_slot.set(None);
+                            referee_action(RefereeAction::SetSlot(turn, None));
+                        }
+                    >"Clear Slot"</Button>
+                </Show>
+                <Show
+                    when=move || can_set_winner
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::SetWinner(Side::Blue))
+                    >"Blue Won"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::SetWinner(Side::Red))
+                    >"Red Won"</Button>
+                </Show>
+                <Show
+                    when=move || can_start_next
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(next_game(draft_id(), team_id().unwrap_or_default(), None, version()).await); })
+                    >"Start Next Game"</Button>
+                </Show>
+                <Show
+                    when=move || !post_draft
+                    fallback=|| view! {}
+                >
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(if paused { RefereeAction::Resume } else { RefereeAction::Pause })
+                    >{ if paused { "Resume" } else { "Pause" } }</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::Reset)
+                    >"Reset"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::Cancel)
+                    >"Cancel Draft"</Button>
+                </Show>
+            </Flex>
+            <Show
+                when=move || !post_draft
+                fallback=|| view! {}
+            >
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::Release(Side::Blue))
+                    >"Release Blue Seat"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::Relink(Side::Blue))
+                    >"New Blue Link"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| { let _ = window().navigator().clipboard().write_text(&blue_url.get()); }
+                    >"Copy Blue Link"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| { let _ = window().navigator().clipboard().write_text(&red_url.get()); }
+                    >"Copy Red Link"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::Relink(Side::Red))
+                    >"New Red Link"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| referee_action(RefereeAction::Release(Side::Red))
+                    >"Release Red Seat"</Button>
+                </Flex>
+            </Show>
+        }.into_any()
+    };
+
+    let pause_controls = move || {
+        let draft = draft.get();
+        match (draft.paused, side()) {
+            (Some(pause), side) => {
+                let paused_by = pause.by.map(|by| draft.team_name(by)).unwrap_or(String::from("Referee"));
+                view! {
+                    <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                        <Button
+                            appearance=ButtonAppearance::Secondary
+                            size=ButtonSize::Large
+                            disabled=true
+                            class="!cursor-default"
+                        >{ format!("Paused By {paused_by}") }</Button>
+                        <Show
+                            when=move || side.is_some() && side == pause.by
+                            fallback=|| view! {}
+                        >
+                            <Button
+                                appearance=ButtonAppearance::Primary
+                                on:click=move |_| spawn_local(async move { show_error(resume(draft_id(), team_id().unwrap_or_default(), token(), version()).await); })
+                                size=ButtonSize::Large
+                            >"Resume"</Button>
+                        </Show>
+                    </Flex>
+                }.into_any()
+            }
+            (None, Some(side)) if !draft.turn.is_post_draft() && draft.pauses_left(side) > 0 => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| spawn_local(async move { show_error(pause(draft_id(), team_id().unwrap_or_default(), token(), version()).await); })
+                        size=ButtonSize::Large
+                    >{ format!("Pause ({} Left)", draft.pauses_left(side)) }</Button>
+                </Flex>
+            }.into_any(),
+            (None, _) => view! {}.into_any(),
+        }
+    };
+
+    let undo_controls = move || {
+        let draft = draft.get();
+        let Some(side) = side() else { return view! {}.into_any(); };
+        let pending = draft.pending_undo(now.get());
+        let seconds = pending.map(|request| (request.expires - now.get()).num_seconds().max(0)).unwrap_or_default();
+        match pending {
+            Some(request) if request.side != side => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on:click=move |_| spawn_local(async move { show_error(approve_undo(draft_id(), team_id().unwrap_or_default(), token(), version()).await); })
+                        size=ButtonSize::Large
+                    >{ format!("Approve {} Undo ({seconds})", draft.team_name(request.side)) }</Button>
+                </Flex>
+            }.into_any(),
+            Some(_) => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        size=ButtonSize::Large
+                        disabled=true
+                        class="!cursor-default"
+                    >{ format!("Undo Requested ({seconds})") }</Button>
+                </Flex>
+            }.into_any(),
+            None if draft.undoable_step().is_some_and(|step| draft.format.acts_in_phase(step, side)) => view! {
+                <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on:click=move |_| spawn_local(async move { show_error(request_undo(draft_id(), team_id().unwrap_or_default(), token(), version()).await); })
+                        size=ButtonSize::Large
+                    >"Undo Last Turn"</Button>
+                </Flex>
+            }.into_any(),
+            None => view! {}.into_any(),
+        }
     };
 
     let download_csv = move || {
-        let csv_data = create_csv(draft.get_untracked());
+        let csv_data = create_csv(draft.get_untracked().into());
         let uint8_array = js_sys::Uint8Array::from(csv_data.as_bytes());
         let blob = web_sys::Blob::new_with_u8_array_sequence(&js_sys::Array::of1(&uint8_array)).unwrap();
         let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
@@ -220,81 +680,39 @@ pub fn Draft() -> impl IntoView {
 
     view! {
         <Grid cols=4>
-            <GridItem class="max-h-screen overflow-hidden flex flex-col items-center blueborders">
-                <video autoplay loop muted class="rotate-180 h-4" class:transparent=move || !(draft.get().turn.is_blue() || (draft.get().turn.is_pre_draft() && draft.get().blue_ready) || draft.get().turn.is_post_draft())>
-                    <source src="https://raw.communitydragon.org/pbe/plugins/rcp-fe-lol-static-assets/global/default/videos/long-progress-bar-main-loop.webm" type="video/webm"/>
-                </video>
-                <div class="justify-evenly h-[5%] flex mt-1">
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_blue_ban_1() class:blue=move || draft.get().blue_ban_1.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan1)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_blue_ban_2() class:blue=move || draft.get().blue_ban_2.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan2)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_blue_ban_3() class:blue=move || draft.get().blue_ban_3.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan3)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_blue_pick_1() class:blue=move || draft.get().blue_pick_1.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick1)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_blue_pick_2() class:blue=move || draft.get().blue_pick_2.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick2)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_blue_pick_3() class:blue=move || draft.get().blue_pick_3.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick3)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <div class="justify-evenly h-[5%] flex">
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_blue_ban_4() class:blue=move || draft.get().blue_ban_4.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan4)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_blue_ban_5() class:blue=move || draft.get().blue_ban_5.is_none() src=MaybeProp::derive(move || pick_image(Turn::BlueBan5)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_blue_pick_4() class:blue=move || draft.get().blue_pick_4.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick4)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_blue_pick_5() class:blue=move || draft.get().blue_pick_5.is_none() src=MaybeProp::derive(move || pick_image(Turn::BluePick5)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-            </GridItem>
-            <GridItem column=2 class="max-h-screen overflow-hidden">
-                <Flex justify=FlexJustify::Center class="pt-4 pb-4 h-[5%]">
-                    <TabList selected_value=selected_role>
-                        {
-                            Role::iter().map(|role| {
-                                view! {
-                                    <Tab value=role.to_string()>
-                                        {role.to_string()}
-                                    </Tab>
-                                }
-                            }).collect_view()
+            <SideColumn
+                side=Side::Blue
+                draft=draft
+                active=Signal::derive(move || draft.get().is_acting(Side::Blue) || (draft.get().turn.is_pre_draft() && draft.get().blue_ready) || draft.get().turn.is_post_draft())
+                on_slot=move |turn: Turn| if team().is_referee() && !draft.get_untracked().turn.is_post_draft() { referee_slot.set(Some(turn)); }
+            />
+            <GridItem column=2 class="max-h-screen overflow-hidden relative">
+                <ChampionGrid
+                    draft=draft
+                    priorities=Signal::derive(move || side().map(|side| draft.get().priorities(side).clone()).unwrap_or_default())
+                    side=Signal::derive(side)
+                    on_select=move |champion_id: u32| {
+                        if let Some(turn) = referee_slot.get_untracked().filter(|_| team().is_referee()) {
+                            referee_slot.set(None);
+                            referee_action(RefereeAction::SetSlot(turn, Some(champion_id)));
+                            return;
                         }
-                    </TabList>
-                    <Input value=search placeholder="Search"/>
-                </Flex>
-                <Scrollbar class="!h-[90%] fade">
-                    <div class="flex flex-wrap justify-center" class:redborders=move || draft.get().turn.is_red() class:blueborders=move || draft.get().turn.is_blue()>
-                        <Suspense
-                            fallback=|| skeleton_view()
-                        >
-                            {
-                                move || if !delay.get() {
-                                    skeleton_view().into_any()
-                                } else {
-                                    view! {}.into_any()
-                                }
-                            }
-                            {
-                                move || match champions.get() {
-                                    None => skeleton_view().into_any(),
-                                    Some(champion) => champion.into_iter().map(|champion| {
-                                        let role: Role = Role::from_str(&selected_role.get()).unwrap_or_default();
-                                        let show = (champion.roles.roles.contains(&role) || role.is_all()) &&
-                                            delay.get() &&
-                                            champion.name.to_lowercase().contains(&search.get().to_lowercase()) &&
-                                            !draft.get().is_champ_chosen(champion.id);
-                                        let is_hovered = draft.get().current_pick().is_some_and(|id| champion.id == id);
-                                        view! {
-                                            <Image
-                                                style:display=move || if show { "block" } else { "none" }
-                                                class:selected=move || is_hovered
-                                                on:click=move |_| if !is_turn() { return; } else { spawn_local(async move { let _ = select_pick(draft_id(), team_id().unwrap_or_default(), champion.id).await; })}
-                                                class="m-2 !w-[75px] !h-[75px] hover:border-4 no-drag !cursor-default"
-                                                src=format!("https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/champion-icons/{}.png", champion.id)
-                                                fit=ImageFit::Fill shape=ImageShape::Rounded
-                                            />
-                                        }
-                                    }).collect_view().into_any()
-                                }
+                        if !is_turn() || !can_act() {
+                            if player().is_some() {
+                                spawn_local(async move { show_error(suggest(draft_id(), team_id().unwrap_or_default(), token(), Some(champion_id)).await); });
                             }
-                        </Suspense>
-                    </div>
-                </Scrollbar>
+                            return;
+                        }
+                        spawn_local(async move { show_error(select_pick(draft_id(), team_id().unwrap_or_default(), token(), version(), acting_step(), champion_id).await); });
+                    }
+                    on_context=move |champion_id: u32| {
+                        if side().is_none() || draft.get_untracked().turn.is_post_draft() { return; }
+                        spawn_local(async move { show_error(toggle_priority(draft_id(), team_id().unwrap_or_default(), token(), champion_id).await); });
+                    }
+                />
                 {
-                    move || match (team().is_spectator(), is_turn(), is_ready(), draft.get().turn) {
-                        (true, _, _, Turn::PreDraft) => view! {
+                    move || match (side().is_none(), is_turn(), is_ready(), draft.get().turn) {
+                        (true, _, _, Turn::PreDraft) if team().is_referee() => view! {
                             <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
                                 <Button
                                     appearance=ButtonAppearance::Secondary
@@ -306,20 +724,45 @@ pub fn Draft() -> impl IntoView {
                                     on:click=move |_| { let _ = window().navigator().clipboard().write_text(&spectator_url.get()); }
                                     size=ButtonSize::Large
                                 >"Copy Spectator Link"</Button>
+                                <Button
+                                    appearance=ButtonAppearance::Secondary
+                                    on:click=move |_| { let _ = window().navigator().clipboard().write_text(&caster_url.get()); }
+                                    size=ButtonSize::Large
+                                >"Copy Caster Link"</Button>
                                 <Button
                                     appearance=ButtonAppearance::Secondary
                                     on:click=move |_| { let _ = window().navigator().clipboard().write_text(&red_url.get()); }
                                     size=ButtonSize::Large
                                 >"Copy Red Link"</Button>
+                                <Button
+                                    appearance=ButtonAppearance::Secondary
+                                    on:click=move |_| { let _ = window().navigator().clipboard().write_text(&referee_url.get()); }
+                                    size=ButtonSize::Large
+                                >"Copy Referee Link"</Button>
+                            </Flex>
+                        }.into_any(),
+                        (true, _, _, Turn::PreDraft) => view! {
+                            <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
+                                <Button
+                                    appearance=ButtonAppearance::Secondary
+                                    size=ButtonSize::Large
+                                    disabled=true
+                                    class="!cursor-default"
+                                >{ move || format!("Waiting On Teams{}", ready_countdown()) }</Button>
                             </Flex>
                         }.into_any(),
                         (false, _, false, Turn::PreDraft) => view! {
                             <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
                                 <Button
                                     appearance=ButtonAppearance::Primary
-                                    on:click=move |_| spawn_local(async move { let _ = ready(draft_id(), team_id().unwrap_or_default()).await; })
+                                    on:click=move |_| spawn_local(async move {
+                                        match ready(draft_id(), team_id().unwrap_or_default(), token(), player_name.get_untracked(), version()).await {
+                                            Ok(Some(pass)) => set_seat_pass.set(Some(pass)),
+                                            result => show_error(result.map(|_| ())),
+                                        }
+                                    })
                                     size=ButtonSize::Large
-                                >{ move || if team().is_blue() { "Ready Blue" } else { "Ready Red" } }</Button>
+                                >{ move || if team().is_blue() { format!("Ready Blue{}", ready_countdown()) } else { format!("Ready Red{}", ready_countdown()) } }</Button>
                             </Flex>
                         }.into_any(),
                         (false, _, true, Turn::PreDraft) => view! {
@@ -329,27 +772,36 @@ pub fn Draft() -> impl IntoView {
                                     size=ButtonSize::Large
                                     disabled=true
                                     class="!cursor-default"
-                                >{ move || if team().is_blue() { "Waiting On Red" } else { "Waiting On Blue" } }</Button>
+                                >{ move || if team().is_blue() { format!("Waiting On Red{}", ready_countdown()) } else { format!("Waiting On Blue{}", ready_countdown()) } }</Button>
                             </Flex>
                         }.into_any(),
                         (false, true, _, _) => view! {
                             <Flex justify=FlexJustify::SpaceEvenly align=FlexAlign::Center class="!h-[5%]">
-                                <Button
-                                    appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
-                                    class="!bg-blue-500 !cursor-default"
-                                ><b style:color="black">{ move || if draft.get().turn.is_blue() { draft_timer.get().clamp(0, 30).to_string() } else { "".to_string() } }</b></Button>
+                                <Flex align=FlexAlign::Center>
+                                    <b class="text-blue-500 w-8 text-center">{ bank_countdown(Side::Blue) }</b>
+                                    <Button
+                                        appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
+                                        class="!bg-blue-500 !cursor-default"
+                                    ><b style:color="black">{ move || if draft.get().is_acting(Side::Blue) { draft_timer.get().to_string() } else { "".to_string() } }</b></Button>
+                                </Flex>
                                 <Button
                                     appearance=ButtonAppearance::Primary
-                                    disabled=Signal::derive(move || draft.get().current_pick().is_none())
-                                    on:click=move |_| spawn_local(async move { let _ = next_turn(draft_id(), team_id().unwrap_or_default()).await; })
+                                    disabled=Signal::derive(move || side().and_then(|side| draft.get().hover(side)).is_none() || !can_act())
+                                    on:click=move |_| {
+                                        spawn_local(async move { show_error(next_turn(draft_id(), team_id().unwrap_or_default(), token(), version(), acting_step()).await); });
+                                    }
                                     size=ButtonSize::Large
                                 >"Confirm"</Button>
-                                <Button
-                                    appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
-                                    class="!bg-red-500 !cursor-default"
-                                ><b style:color="black">{ move || if draft.get().turn.is_red() { draft_timer.get().clamp(0, 30).to_string() } else { "".to_string() } }</b></Button>
+                                <Flex align=FlexAlign::Center>
+                                    <Button
+                                        appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
+                                        class="!bg-red-500 !cursor-default"
+                                    ><b style:color="black">{ move || if draft.get().is_acting(Side::Red) { draft_timer.get().to_string() } else { "".to_string() } }</b></Button>
+                                    <b class="text-red-500 w-8 text-center">{ bank_countdown(Side::Red) }</b>
+                                </Flex>
                             </Flex>
                         }.into_any(),
+                        (_, _, _, Turn::PostDraft) if draft.get().series_in_progress() => series_controls(),
                         (_, _, _, Turn::PostDraft) => view! {
                             <Flex justify=FlexJustify::Center align=FlexAlign::Center class="!h-[5%]">
                                 <Button
@@ -362,63 +814,68 @@ pub fn Draft() -> impl IntoView {
                         }.into_any(),
                         (_, _, _, _) => view! {
                             <Flex justify=FlexJustify::SpaceEvenly align=FlexAlign::Center class="!h-[5%]">
-                                <Button
-                                    appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
-                                    class="!bg-blue-500 !cursor-default"
-                                    ><b style:color="black">{ move || if draft.get().turn.is_blue() { draft_timer.get().clamp(0, 30).to_string() } else { "".to_string() } }</b></Button>
+                                <Flex align=FlexAlign::Center>
+                                    <b class="text-blue-500 w-8 text-center">{ bank_countdown(Side::Blue) }</b>
+                                    <Button
+                                        appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
+                                        class="!bg-blue-500 !cursor-default"
+                                        ><b style:color="black">{ move || if draft.get().is_acting(Side::Blue) { draft_timer.get().to_string() } else { "".to_string() } }</b></Button>
+                                </Flex>
                                 <Button
                                     appearance=ButtonAppearance::Primary class="!cursor-default" disabled=true
-                                >{ move || if draft.get().turn.is_blue() { "Blue Turn" } else { "Red Turn" } }</Button>
-                                <Button
-                                    appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
-                                    class="!bg-red-500 !cursor-default"
-                                ><b style:color="black">{ move || if draft.get().turn.is_red() { draft_timer.get().clamp(0, 30).to_string() } else { "".to_string() } }</b></Button>
+                                >{ move || match (draft.get().is_acting(Side::Blue), draft.get().is_acting(Side::Red)) {
+                                    (true, true) => "Both Teams",
+                                    (true, false) => "Blue Turn",
+                                    (false, true) => "Red Turn",
+                                    (false, false) => "",
+                                } }</Button>
+                                <Flex align=FlexAlign::Center>
+                                    <Button
+                                        appearance=ButtonAppearance::Secondary shape=ButtonShape::Circular disabled=true size=ButtonSize::Large
+                                        class="!bg-red-500 !cursor-default"
+                                    ><b style:color="black">{ move || if draft.get().is_acting(Side::Red) { draft_timer.get().to_string() } else { "".to_string() } }</b></Button>
+                                    <b class="text-red-500 w-8 text-center">{ bank_countdown(Side::Red) }</b>
+                                </Flex>
                             </Flex>
                         }.into_any(),
                     }
                 }
-            </GridItem>
-            <GridItem class="max-h-screen overflow-hidden flex flex-col items-center redborders">
-                <video autoplay loop muted class="red h-4" class:transparent=move || !(draft.get().turn.is_red() || (draft.get().turn.is_pre_draft() && draft.get().red_ready) || draft.get().turn.is_post_draft())>
-                    <source src="https://raw.communitydragon.org/pbe/plugins/rcp-fe-lol-static-assets/global/default/videos/long-progress-bar-main-loop.webm" type="video/webm"/>
-                </video>
-                <div class="justify-evenly h-[5%] flex mt-1">
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_red_ban_1() src=MaybeProp::derive(move || pick_image(Turn::RedBan1)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_red_ban_2() src=MaybeProp::derive(move || pick_image(Turn::RedBan2)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_red_ban_3() src=MaybeProp::derive(move || pick_image(Turn::RedBan3)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_red_pick_1() src=MaybeProp::derive(move || pick_image(Turn::RedPick1)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_red_pick_2() src=MaybeProp::derive(move || pick_image(Turn::RedPick2)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_red_pick_3() src=MaybeProp::derive(move || pick_image(Turn::RedPick3)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <div class="justify-evenly h-[5%] flex">
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_red_ban_4() src=MaybeProp::derive(move || pick_image(Turn::RedBan4)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
-                    <Image class="w-fit no-drag aspect-square" class:selected=move || draft.get().turn.is_red_ban_5() src=MaybeProp::derive(move || pick_image(Turn::RedBan5)) fit=ImageFit::Fill shape=ImageShape::Rounded/>
+                // Controls that only show up some of the time float over the bottom of the grid.
+                <div class="absolute bottom-[6%] left-1/2 -translate-x-1/2 flex flex-col gap-1">
+                    { player_controls }
+                    { team_controls }
+                    { pause_controls }
+                    { undo_controls }
+                    { referee_controls }
                 </div>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_red_pick_4() src=MaybeProp::derive(move || pick_image(Turn::RedPick4)) fit=ImageFit::Fill shape=ImageShape::Circular/>
-                <Image class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=move || draft.get().turn.is_red_pick_5() src=MaybeProp::derive(move || pick_image(Turn::RedPick5)) fit=ImageFit::Fill shape=ImageShape::Circular/>
             </GridItem>
+            <SideColumn
+                side=Side::Red
+                draft=draft
+                active=Signal::derive(move || draft.get().is_acting(Side::Red) || (draft.get().turn.is_pre_draft() && draft.get().red_ready) || draft.get().turn.is_post_draft())
+                on_slot=move |turn: Turn| if team().is_referee() { referee_slot.set(Some(turn)); }
+            />
         </Grid>
     }
 }
 
-fn create_csv(draft: Draft) -> String {
-    let mut writer = WriterBuilder::new().from_writer(vec![]);
-    let _ = writer.serialize(draft);
-    let csv = String::from_utf8(writer.into_inner().unwrap_or_default()).unwrap_or_default();
-    csv
-}
+/// Returns a handler that shows the error of a failed server function as a toast.
+pub(crate) fn use_error_toast() -> impl Fn(Result<(), ServerFnError<DraftError>>) + Copy + Send + Sync + 'static {
+    let draft_error = RwSignal::new(None::<String>);
 
-fn skeleton_view() -> impl IntoView {
-    view! {
-        <Skeleton class="flex flex-wrap justify-center">
-            {
-                (0..169).into_iter().map(|_| {
-                    view! {
-                        <SkeletonItem class="m-2 !w-[75px] !h-[75px]"/>
-                    }
-                }).collect_view()
-            }
-        </Skeleton>
+    Effect::new(move |_| {
+        let Some(message) = draft_error.get() else { return; };
+
+        let toaster = ToasterInjection::expect_context();
+        toaster.dispatch_toast(move || view! {
+            <Toast>
+                <ToastTitle>{message}</ToastTitle>
+            </Toast>
+        }, ToastOptions::default().with_position(ToastPosition::Bottom).with_intent(ToastIntent::Error));
+    });
+
+    move |result| if let Err(err) = result {
+        draft_error.set(Some(err.to_string()));
     }
 }
 
@@ -430,83 +887,541 @@ pub async fn get_champions() -> Result<Vec<champion::Model>, ServerFnError> {
     champion::Entity::find().all(&db).await.map_err(|err| ServerFnError::new(err.to_string()))
 }
 
+#[server]
+async fn server_time() -> Result<DateTime<Utc>, ServerFnError> {
+    Ok(Utc::now())
+}
+
 #[server]
 async fn check_for_draft(draft_id: String) -> Result<bool, ServerFnError> {
-    let ss: leptos_ws::server_signals::ServerSignals = use_context::<crate::AppState>().ok_or_else(|| ServerFnError::new("Database connection missing."))?.server_signals;
-    if !ss.contains(&draft_id).await {
-        return Ok(false);
-    }
+    let registry = use_context::<crate::AppState>().ok_or_else(|| ServerFnError::new("Database connection missing."))?.registry;
+    let Ok(draft_id) = Uuid::from_str(&draft_id) else { return Ok(false); };
 
-    use leptos_ws::ServerSignal;
-    let draft: ServerSignal<Draft> = ServerSignal::new(draft_id.clone(), Draft::default()).unwrap();
-    let draft_value = draft.get();
+    Ok(registry.contains(&draft_id))
+}
 
-    if &draft_value.draft_id.to_string() == "00000000-0000-0000-0000-000000000000" {
-        return Ok(false);
+/// The live signal of a draft this server is running.
+#[cfg(feature = "ssr")]
+fn live_draft(draft_id: &str) -> Result<leptos_ws::ServerSignal<Draft>, DraftError> {
+    let registry = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.registry;
+    let draft_id = Uuid::from_str(draft_id).map_err(|_| DraftError::DraftNotFound)?;
+    registry.signal(&draft_id).ok_or(DraftError::DraftNotFound)
+}
+
+/// The side a team link speaks for. Once anyone has joined the team, only its players can send commands for it,
+/// so a leaked link alone can't.
+#[cfg(feature = "ssr")]
+fn team_player(draft: &Draft, team_id: &Uuid, token: Option<&Uuid>) -> Result<Side, DraftError> {
+    let side = draft.team_side(team_id).ok_or(DraftError::NotInDraft)?;
+    if !draft.players(side).is_empty() && draft.player(side, token).is_none() {
+        return Err(DraftError::NotJoined);
     }
+    Ok(side)
+}
 
-    Ok(true)
+/// Like `team_player`, for decisions the whole team lives with, which are the captain's to make.
+#[cfg(feature = "ssr")]
+fn team_captain(draft: &Draft, team_id: &Uuid, token: Option<&Uuid>) -> Result<Side, DraftError> {
+    let side = team_player(draft, team_id, token)?;
+    if !draft.players(side).is_empty() && !draft.is_captain(side, token) {
+        return Err(DraftError::NotCaptain);
+    }
+    Ok(side)
 }
 
+/// Readies a team and claims its seat, locking the roster to the players already joined. When nobody has joined
+/// yet, the caller is seated as captain and gets back the pass their browser should keep.
 #[server]
-async fn ready(draft_id: String, team_id: String) -> Result<(), ServerFnError> {
-    use leptos_ws::ServerSignal;
+async fn ready(draft_id: String, team_id: String, token: Option<String>, name: String, version: u32) -> Result<Option<SeatPass>, ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+    let pass = SeatPass { player_id: Uuid::new_v4(), token: Uuid::new_v4() };
 
-    let draft: ServerSignal<Draft> = ServerSignal::new(draft_id, Draft::default()).unwrap();
-    let draft_value = draft.get();
+    let seated = draft.try_update(move |value| {
+        if value.version != version || !value.turn.is_pre_draft() {
+            return Err(DraftError::StaleTurn);
+        }
+        if value.paused.is_some() {
+            return Err(DraftError::Paused);
+        }
+        let side = value.team_side(&team_id).ok_or(DraftError::NotInDraft)?;
+        let joined = value.player(side, token.as_ref()).is_some();
+        if value.is_claimed(side) && !joined {
+            return Err(DraftError::SeatClaimed);
+        }
 
-    let Ok(team_id) = Uuid::from_str(&team_id) else { return Err(ServerFnError::new("Invalid team_id.")); };
+        let seated = if value.players(side).is_empty() {
+            let name = Some(name.trim().to_string()).filter(|name| !name.is_empty()).unwrap_or_else(|| value.team_name(side));
+            value.join(side, pass, name);
+            Some(pass)
+        } else if !joined {
+            return Err(DraftError::NotJoined);
+        } else {
+            None
+        };
 
-    if !(draft_value.blue_id == team_id || draft_value.red_id == team_id) {
-        return Err(ServerFnError::new("Does not have the correct team uuid for the selection."));
-    }
+        value.claim(side);
+        value.ready(side);
+        Ok(seated)
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(seated)
+}
 
-    if draft_value.blue_id == team_id {
-        draft.update(move |value| value.blue_ready = true);
-    }
+#[server]
+async fn select_pick(draft_id: String, team_id: String, token: Option<String>, version: u32, step: usize, pick: u32) -> Result<(), ServerFnError<DraftError>> {
+    use sea_orm::EntityTrait;
 
-    if draft_value.red_id == team_id {
-        draft.update(move |value| value.red_ready = true);
+    let db = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.db;
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    if champion::Entity::find_by_id(pick).one(&db).await.map_err(DraftError::from)?.is_none() {
+        return Err(DraftError::UnknownChampion.into());
     }
 
-    let draft_value = draft.get();
-    if draft_value.blue_ready && draft_value.red_ready {
-        draft.update(move |value| value.next_turn());
+    draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        if value.paused.is_some() {
+            return Err(DraftError::Paused);
+        }
+        let side = value.team_side(&team_id).filter(|side| value.is_acting(*side)).ok_or(DraftError::NotYourTurn)?;
+        if value.side_step(side) != Some(step) {
+            return Err(DraftError::StaleTurn);
+        }
+        if !value.can_act(side, token.as_ref()) {
+            return Err(DraftError::NotYourPick);
+        }
+        if value.is_champ_chosen(pick, Some(side)) {
+            return Err(DraftError::ChampionUnavailable);
+        }
+
+        value.select_pick(side, pick);
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Adds or removes a champion from the team's priorities, which the `Priority` timeout policy picks from.
+#[server]
+async fn toggle_priority(draft_id: String, team_id: String, token: Option<String>, champion_id: u32) -> Result<(), ServerFnError<DraftError>> {
+    use sea_orm::EntityTrait;
+
+    let db = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.db;
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    if champion::Entity::find_by_id(champion_id).one(&db).await.map_err(DraftError::from)?.is_none() {
+        return Err(DraftError::UnknownChampion.into());
     }
 
+    draft.try_update(move |value| {
+        let side = team_player(value, &team_id, token.as_ref())?;
+
+        value.toggle_priority(side, champion_id);
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
     Ok(())
 }
 
 #[server]
-async fn select_pick(draft_id: String, team_id: String, pick: u32) -> Result<(), ServerFnError> {
-    use leptos_ws::ServerSignal;
+async fn next_turn(draft_id: String, team_id: String, token: Option<String>, version: u32, step: usize) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
 
-    let draft: ServerSignal<Draft> = ServerSignal::new(draft_id, Draft::default()).unwrap();
+    draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        if value.paused.is_some() {
+            return Err(DraftError::Paused);
+        }
+        let side = value.team_side(&team_id).filter(|side| value.is_acting(*side)).ok_or(DraftError::NotYourTurn)?;
+        if value.side_step(side) != Some(step) {
+            return Err(DraftError::StaleTurn);
+        }
+        if !value.can_act(side, token.as_ref()) {
+            return Err(DraftError::NotYourPick);
+        }
+        if value.hover(side).is_none() {
+            return Err(DraftError::NothingHovered);
+        }
 
-    let Ok(team_id) = Uuid::from_str(&team_id) else { return Err(ServerFnError::new("Invalid team_id.")); };
-    let draft_value = draft.get();
-    if !((draft_value.turn.is_blue() && draft_value.blue_id == team_id) || (draft_value.turn.is_red() && draft_value.red_id == team_id)) {
-        return Err(ServerFnError::new("Does not have the correct team uuid for the selection."));
+        value.lock_in(side);
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Seats a player under a team link, or renames them. Returns the player's pass, minting one for new players.
+/// Once a team has claimed its seat only its players can rejoin, except that a released seat is claimed again
+/// by the first browser to join it mid-draft.
+#[server]
+async fn join_team(draft_id: String, team_id: String, token: Option<String>, name: String) -> Result<SeatPass, ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+    let fresh = SeatPass { player_id: Uuid::new_v4(), token: Uuid::new_v4() };
+
+    let pass = draft.try_update(move |value| {
+        let side = value.team_side(&team_id).ok_or(DraftError::NotInDraft)?;
+        let pass = match value.player(side, token.as_ref()) {
+            Some(player) => SeatPass { player_id: player.player_id, token: player.token },
+            None if value.is_claimed(side) => return Err(DraftError::SeatClaimed),
+            None => fresh,
+        };
+
+        value.join(side, pass, name.trim().to_string());
+        if value.is_ready(side) {
+            value.claim(side);
+        }
+        Ok(pass)
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(pass)
+}
+
+/// Hands the captaincy to another player on the team.
+#[server]
+async fn make_captain(draft_id: String, team_id: String, token: Option<String>, captain_id: String) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+    let captain_id = Uuid::from_str(&captain_id).map_err(|_| DraftError::NotJoined)?;
+
+    draft.try_update(move |value| {
+        let side = team_captain(value, &team_id, token.as_ref())?;
+        if !value.make_captain(side, &captain_id) {
+            return Err(DraftError::NotJoined);
+        }
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Sets or clears the champion a player would like for their own pick.
+#[server]
+async fn suggest(draft_id: String, team_id: String, token: Option<String>, champion_id: Option<u32>) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    draft.try_update(move |value| {
+        let side = team_player(value, &team_id, token.as_ref())?;
+        // Suggestions belong to a player, so an open roster isn't enough here.
+        let Some(token) = token.filter(|token| value.player(side, Some(token)).is_some()) else {
+            return Err(DraftError::NotJoined);
+        };
+        if champion_id.is_some_and(|champion_id| value.is_champ_chosen(champion_id, Some(side))) {
+            return Err(DraftError::ChampionUnavailable);
+        }
+
+        value.suggest(side, &token, champion_id);
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Updates a team's name, tag and logo while it hasn't readied up yet.
+#[server]
+async fn set_team(draft_id: String, team_id: String, token: Option<String>, team: TeamInfo) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    draft.try_update(move |value| {
+        let side = team_captain(value, &team_id, token.as_ref())?;
+        let ready = match side {
+            Side::Blue => value.blue_ready,
+            Side::Red => value.red_ready,
+        };
+        if !value.turn.is_pre_draft() || ready {
+            return Err(DraftError::StaleTurn);
+        }
+
+        value.set_team(side, TeamInfo {
+            name: team.name.trim().to_string(),
+            tag: team.tag.trim().to_string(),
+            logo: team.logo.map(|logo| logo.trim().to_string()).filter(|logo| !logo.is_empty()),
+        });
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Calls one of the team's tech pauses, stopping every clock until it resumes the draft.
+#[server]
+async fn pause(draft_id: String, team_id: String, token: Option<String>, version: u32) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    draft.try_update(move |value| {
+        if value.version != version || value.turn.is_post_draft() {
+            return Err(DraftError::StaleTurn);
+        }
+        if value.paused.is_some() {
+            return Err(DraftError::Paused);
+        }
+        let side = team_captain(value, &team_id, token.as_ref())?;
+        if value.pauses_left(side) == 0 {
+            return Err(DraftError::NoPausesLeft);
+        }
+
+        value.pause(Some(side));
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+#[server]
+async fn resume(draft_id: String, team_id: String, token: Option<String>, version: u32) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        let side = team_captain(value, &team_id, token.as_ref())?;
+        let pause = value.paused.ok_or(DraftError::NotPaused)?;
+        if pause.by != Some(side) {
+            return Err(DraftError::NotYourPause);
+        }
+
+        value.resume(Some(side));
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Asks the other team to let the acting team take back the turn it last locked.
+#[server]
+async fn request_undo(draft_id: String, team_id: String, token: Option<String>, version: u32) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        let side = team_captain(value, &team_id, token.as_ref())?;
+        let step = value.undoable_step().ok_or(DraftError::NothingToUndo)?;
+        if !value.format.acts_in_phase(step, side) {
+            return Err(DraftError::NotYourUndo);
+        }
+
+        value.request_undo(side, step);
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Approves the other team's undo request. The referee link can approve either team's.
+#[server]
+async fn approve_undo(draft_id: String, team_id: String, token: Option<String>, version: u32) -> Result<(), ServerFnError<DraftError>> {
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        let request = value.pending_undo(Utc::now()).ok_or(DraftError::UndoExpired)?;
+        if value.is_admin(&team_id) {
+            value.as_referee(|draft| draft.undo());
+            return Ok(());
+        }
+        let side = team_captain(value, &team_id, token.as_ref())?;
+        if request.side == side {
+            return Err(DraftError::OwnUndo);
+        }
+
+        value.undo();
+        Ok(())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+    Ok(())
+}
+
+/// Applies a referee's override. Every event it records is flagged as the referee's in the event log.
+#[server]
+async fn referee(draft_id: String, admin_id: String, version: u32, action: RefereeAction) -> Result<(), ServerFnError<DraftError>> {
+    use sea_orm::EntityTrait;
+
+    let app_state = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?;
+    let draft = live_draft(&draft_id)?;
+    let admin_id = Uuid::from_str(&admin_id).map_err(|_| DraftError::InvalidTeam)?;
+    let new_link = Uuid::new_v4();
+
+    if let RefereeAction::SetSlot(_, Some(pick)) = action {
+        if champion::Entity::find_by_id(pick).one(&app_state.db).await.map_err(DraftError::from)?.is_none() {
+            return Err(DraftError::UnknownChampion.into());
+        }
     }
 
-    draft.update(move |value| value.select_pick(pick));
+    let draft_value = draft.try_update(move |value| {
+        if !value.is_admin(&admin_id) {
+            return Err(DraftError::NotReferee);
+        }
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+
+        match action {
+            RefereeAction::Ready(side) if value.turn.is_pre_draft() => value.as_referee(|draft| draft.ready(side)),
+            RefereeAction::Advance if value.turn.side().is_some() => value.as_referee(|draft| draft.advance()),
+            RefereeAction::Revert => {
+                let step = value.undoable_step().ok_or(DraftError::NothingToUndo)?;
+                let side = value.format.turn(step).side();
+                value.as_referee(|draft| draft.revert(step, side));
+            }
+            // Slots are only persisted as the draft finishes, so a post-draft override would never be saved.
+            RefereeAction::SetSlot(..) if value.turn.is_post_draft() => return Err(DraftError::StaleTurn),
+            RefereeAction::SetSlot(turn, pick) => {
+                let already_set = |pick: u32| value.selections().any(|selection| selection == (turn, pick));
+                if pick.is_some_and(|pick| !already_set(pick) && value.is_champ_chosen(pick, None)) {
+                    return Err(DraftError::ChampionUnavailable);
+                }
+                value.as_referee(|draft| draft.override_slot(turn, pick));
+            }
+            RefereeAction::Pause if value.paused.is_none() && !value.turn.is_post_draft() => value.as_referee(|draft| draft.pause(None)),
+            RefereeAction::Resume if value.paused.is_some() => value.as_referee(|draft| draft.resume(None)),
+            RefereeAction::Reset if !value.turn.is_post_draft() => value.as_referee(|draft| draft.reset()),
+            RefereeAction::Cancel => value.as_referee(|draft| draft.record(EventKind::Cancel, None, None)),
+            RefereeAction::Release(side) => value.as_referee(|draft| draft.release(side)),
+            RefereeAction::Relink(side) => value.as_referee(|draft| draft.relink(side, new_link)),
+            RefereeAction::SetWinner(side) if value.turn.is_post_draft() && value.best_of.is_some() && value.next_draft_id.is_none() => value.as_referee(|draft| draft.set_winner(side)),
+            RefereeAction::SetWinner(_) => return Err(DraftError::ResultUnavailable),
+            _ => return Err(DraftError::StaleTurn),
+        }
+        Ok(value.clone())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+
+    if let RefereeAction::SetWinner(_) = action {
+        record_winner(&app_state, draft_value).await?;
+    }
+    if action == RefereeAction::Cancel {
+        let draft_id = Uuid::from_str(&draft_id).map_err(|_| DraftError::DraftNotFound)?;
+        crate::lifecycle::cancel_draft(&app_state, draft_id).await.map_err(DraftError::from)?;
+    }
     Ok(())
 }
 
 #[server]
-async fn next_turn(draft_id: String, team_id: String) -> Result<(), ServerFnError> {
-    use leptos_ws::ServerSignal;
+async fn report_winner(draft_id: String, team_id: String, token: Option<String>, version: u32, winner: Side) -> Result<(), ServerFnError<DraftError>> {
+    let app_state = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?;
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    let draft_value = draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        let side = team_captain(value, &team_id, token.as_ref())?;
+        if !value.turn.is_post_draft() || value.winner.is_some() {
+            return Err(DraftError::ResultUnavailable);
+        }
 
-    let draft: ServerSignal<Draft> = ServerSignal::new(draft_id.clone(), Draft::default()).unwrap();
-    let draft_timer: ServerSignal<i32> = ServerSignal::new(format!("{draft_id}timer"), 30).unwrap();
+        value.report_winner(side, winner);
+        Ok(value.clone())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
 
-    let Ok(team_id) = Uuid::from_str(&team_id) else { return Err(ServerFnError::new("Invalid team_id.")); };
-    let draft_value = draft.get();
-    if !((draft_value.turn.is_blue() && draft_value.blue_id == team_id) || (draft_value.turn.is_red() && draft_value.red_id == team_id)) {
-        return Err(ServerFnError::new("Does not have the correct team uuid for the selection."));
+    if draft_value.winner.is_some() {
+        record_winner(&app_state, draft_value).await?;
     }
-    
-    draft.update(move |value| value.next_turn());
-    draft_timer.update(move |value| *value = 30);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Stores a game's result. The next game waits on someone starting it, so the referee can still correct the result.
+#[cfg(feature = "ssr")]
+async fn record_winner(app_state: &crate::AppState, draft_value: Draft) -> Result<(), DraftError> {
+    use sea_orm::{sea_query, EntityTrait};
+    use crate::entity::draft as draft_entity;
+
+    let model: draft_entity::ActiveModel = draft_entity::Model::from(draft_value.clone()).into();
+    draft_entity::Entity::insert(model)
+        .on_conflict(
+            sea_query::OnConflict::column(draft_entity::Column::DraftId)
+                .update_column(draft_entity::Column::Winner)
+                .to_owned()
+        )
+        .exec(&app_state.db)
+        .await?;
+    Ok(())
+}
+
+/// Starts the next game of a series whose sides alternate, once this one's result stands. Either captain or the
+/// referee can start it.
+#[server]
+async fn next_game(draft_id: String, team_id: String, token: Option<String>, version: u32) -> Result<(), ServerFnError<DraftError>> {
+    use crate::series::start_next_game;
+
+    let app_state = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?;
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    // Claiming the version up front means a second click can't start another game while this one is created.
+    let draft_value = draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        if !value.is_admin(&team_id) {
+            team_captain(value, &team_id, token.as_ref())?;
+        }
+        if value.winner.is_none() {
+            return Err(DraftError::ResultUnavailable);
+        }
+        if !value.side_selection.is_alternate() || !value.series_in_progress() || value.next_draft_id.is_some() {
+            return Err(DraftError::NoNextGame);
+        }
+
+        value.version += 1;
+        Ok(value.clone())
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+
+    let next_draft_id = start_next_game(&app_state, &draft_value, true).await.map_err(DraftError::from)?;
+    draft.update(move |value| value.next_draft_id = Some(next_draft_id));
+
+    Ok(())
+}
+
+#[server]
+async fn choose_side(draft_id: String, team_id: String, token: Option<String>, version: u32, side: Side) -> Result<(), ServerFnError<DraftError>> {
+    use crate::series::start_next_game;
+
+    let app_state = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?;
+    let draft = live_draft(&draft_id)?;
+    let team_id = Uuid::from_str(&team_id).map_err(|_| DraftError::InvalidTeam)?;
+    let token = token.and_then(|token| Uuid::from_str(&token).ok());
+
+    // Claiming the version up front means a second click can't start another game while this one is created.
+    let (draft_value, loser) = draft.try_update(move |value| {
+        if value.version != version {
+            return Err(DraftError::StaleTurn);
+        }
+        let loser = value.winner.map(|winner| winner.opponent()).ok_or(DraftError::ResultUnavailable)?;
+        if team_captain(value, &team_id, token.as_ref())? != loser {
+            return Err(DraftError::NotLoser);
+        }
+        if !value.series_in_progress() || value.next_draft_id.is_some() {
+            return Err(DraftError::NoNextGame);
+        }
+
+        value.version += 1;
+        Ok((value.clone(), loser))
+    }).unwrap_or(Err(DraftError::DraftNotFound))?;
+
+    let next_draft_id = start_next_game(&app_state, &draft_value, side != loser).await.map_err(DraftError::from)?;
+    draft.update(move |value| value.next_draft_id = Some(next_draft_id));
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use gloo_timers::callback::Timeout;
+use leptos::prelude::*;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use thaw::*;
+use crate::api::Role;
+use crate::draft::draft::get_champions;
+use crate::format::Side;
+use crate::Draft;
+
+/// The role tabs, search box and champion icons. Champions already chosen elsewhere in `draft` are hidden, as
+/// `side` sees them when it's given.
+#[component]
+pub fn ChampionGrid(
+    #[prop(into)] draft: Signal<Draft>,
+    #[prop(into)] on_select: Callback<u32>,
+    #[prop(optional, into)] on_context: Option<Callback<u32>>,
+    #[prop(optional, into)] priorities: MaybeProp<Vec<u32>>,
+    #[prop(optional, into)] side: MaybeProp<Side>,
+) -> impl IntoView {
+    let selected_role = RwSignal::new(Role::default().to_string());
+    let delay = RwSignal::new(false);
+    let search = RwSignal::new(String::new());
+
+    let champions = Resource::new(|| (), move |_| async move {
+        if let Ok(champs) = get_champions().await {
+            champs
+        } else {
+            vec![]
+        }
+    });
+
+    Effect::new(move |_| {
+        let _ = champions.get();
+        let timeout = Timeout::new(1_000, move || {
+            delay.set(true);
+        });
+        timeout.forget();
+    });
+
+    view! {
+        <Flex justify=FlexJustify::Center class="pt-4 pb-4 h-[5%]">
+            <TabList selected_value=selected_role>
+                {
+                    Role::iter().map(|role| {
+                        view! {
+                            <Tab value=role.to_string()>
+                                {role.to_string()}
+                            </Tab>
+                        }
+                    }).collect_view()
+                }
+            </TabList>
+            <Input value=search placeholder="Search"/>
+        </Flex>
+        <Scrollbar class="!h-[90%] fade">
+            <div class="flex flex-wrap justify-center" class:redborders=move || draft.get().turn.is_red() class:blueborders=move || draft.get().turn.is_blue()>
+                <Suspense
+                    fallback=|| skeleton_view()
+                >
+                    {
+                        move || if !delay.get() {
+                            skeleton_view().into_any()
+                        } else {
+                            view! {}.into_any()
+                        }
+                    }
+                    {
+                        move || match champions.get() {
+                            None => skeleton_view().into_any(),
+                            Some(champion) => champion.into_iter().map(|champion| {
+                                let role: Role = Role::from_str(&selected_role.get()).unwrap_or_default();
+                                let show = (champion.roles.roles.contains(&role) || role.is_all()) &&
+                                    delay.get() &&
+                                    champion.name.to_lowercase().contains(&search.get().to_lowercase()) &&
+                                    !draft.get().is_champ_chosen(champion.id, side.get());
+                                let is_hovered = draft.get().hovers().contains(&champion.id);
+                                let is_priority = priorities.get().is_some_and(|priorities| priorities.contains(&champion.id));
+                                view! {
+                                    <Image
+                                        style:display=move || if show { "block" } else { "none" }
+                                        class:selected=move || is_hovered
+                                        class:priority=move || is_priority
+                                        on:click=move |_| on_select.run(champion.id)
+                                        on:contextmenu=move |event| {
+                                            let Some(on_context) = on_context else { return; };
+                                            event.prevent_default();
+                                            on_context.run(champion.id);
+                                        }
+                                        class="m-2 !w-[75px] !h-[75px] hover:border-4 no-drag !cursor-default"
+                                        src=format!("https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/champion-icons/{}.png", champion.id)
+                                        fit=ImageFit::Fill shape=ImageShape::Rounded
+                                    />
+                                }
+                            }).collect_view().into_any()
+                        }
+                    }
+                </Suspense>
+            </div>
+        </Scrollbar>
+    }
+}
+
+fn skeleton_view() -> impl IntoView {
+    view! {
+        <Skeleton class="flex flex-wrap justify-center">
+            {
+                (0..169).into_iter().map(|_| {
+                    view! {
+                        <SkeletonItem class="m-2 !w-[75px] !h-[75px]"/>
+                    }
+                }).collect_view()
+            }
+        </Skeleton>
+    }
+}
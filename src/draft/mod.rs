@@ -1,2 +1,6 @@
 pub mod draft;
-pub mod completed;
\ No newline at end of file
+pub mod completed;
+pub mod grid;
+pub mod replay;
+pub mod sandbox;
+pub mod side;
\ No newline at end of file
@@ -0,0 +1,86 @@
+use leptos::prelude::*;
+use thaw::*;
+use crate::entity::champion;
+use crate::event::{DraftEvent, EventKind};
+
+/// Elapsed time since the first event, as `m:ss`.
+fn elapsed(events: &[DraftEvent], event: &DraftEvent) -> String {
+    let seconds = events.first().map(|first| (event.at - first.at).num_seconds()).unwrap_or_default().max(0);
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn describe(event: &DraftEvent, champions: &[champion::Model]) -> String {
+    let side = event.side.map(|side| format!("{side} ")).unwrap_or_default();
+    let champion = event.champion
+        .map(|id| champions.iter().find(|champion| champion.id == id).map(|champion| champion.name.clone()).unwrap_or(id.to_string()))
+        .map(|name| format!(" {name}"))
+        .unwrap_or_default();
+    match event.kind {
+        EventKind::Ready => format!("{side}ready"),
+        EventKind::Hover => format!("{side}hovered{champion}"),
+        EventKind::Lock => format!("{side}locked in{champion}"),
+        EventKind::Timeout if champion.is_empty() => format!("{side}timed out"),
+        EventKind::Timeout => format!("{side}timed out,{champion} auto-filled"),
+        EventKind::Pause => "Paused".to_string(),
+        EventKind::Resume => "Resumed".to_string(),
+        EventKind::Revert => format!("{side}turn reverted"),
+        EventKind::Set if champion.is_empty() => format!("{side}slot cleared"),
+        EventKind::Set => format!("{side}slot set to{champion}"),
+        EventKind::Reset => "Reset".to_string(),
+        EventKind::Cancel => "Cancelled".to_string(),
+        EventKind::Release => format!("{side}seat released"),
+        EventKind::Relink => format!("{side}link replaced"),
+        EventKind::Clash => format!("{side}pick clashed, picking again"),
+        EventKind::Winner => format!("{side}won"),
+    }
+}
+
+/// Scrubs through a completed draft's events. `position` is how many of them have been applied.
+#[component]
+pub fn ReplayControls(
+    #[prop(into)] events: Signal<Vec<DraftEvent>>,
+    position: RwSignal<usize>,
+    #[prop(into)] champions: Signal<Vec<champion::Model>>,
+) -> impl IntoView {
+    let ends_turn = |event: &DraftEvent| event.kind.is_lock() || event.kind.is_timeout();
+
+    let previous_turn = move |_| {
+        let events = events.get_untracked();
+        let current = position.get_untracked();
+        let previous = events[..current.saturating_sub(1).min(events.len())].iter().rposition(ends_turn);
+        position.set(previous.map(|index| index + 1).unwrap_or(0));
+    };
+
+    let next_turn = move |_| {
+        let events = events.get_untracked();
+        let current = position.get_untracked().min(events.len());
+        let next = events[current..].iter().position(ends_turn);
+        position.set(next.map(|index| current + index + 1).unwrap_or(events.len()));
+    };
+
+    let label = move || {
+        let events = events.get();
+        match position.get().checked_sub(1).and_then(|index| events.get(index)) {
+            Some(event) => format!("{} {}", elapsed(&events, event), describe(event, &champions.get())),
+            None => "Start".to_string(),
+        }
+    };
+
+    view! {
+        <Flex vertical=true align=FlexAlign::Center class="w-full">
+            <input
+                type="range"
+                class="w-full"
+                min="0"
+                max=move || events.get().len()
+                prop:value=move || position.get()
+                on:input=move |event| position.set(event_target_value(&event).parse().unwrap_or_default())
+            />
+            <Flex justify=FlexJustify::Center align=FlexAlign::Center>
+                <Button appearance=ButtonAppearance::Secondary on:click=previous_turn>"Previous Turn"</Button>
+                <Button appearance=ButtonAppearance::Secondary on:click=next_turn>"Next Turn"</Button>
+            </Flex>
+            <Button appearance=ButtonAppearance::Secondary class="!cursor-default" disabled=true>{ label }</Button>
+        </Flex>
+    }
+}
@@ -0,0 +1,220 @@
+use codee::string::JsonSerdeCodec;
+use leptos::prelude::*;
+use leptos::Params;
+use leptos::task::spawn_local;
+use leptos_router::hooks::{use_navigate, use_params, use_query_map};
+use leptos_router::params::Params;
+use leptos_use::storage::use_local_storage;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thaw::*;
+use uuid::Uuid;
+use crate::draft::draft::use_error_toast;
+use crate::draft::grid::ChampionGrid;
+use crate::draft::side::SideColumn;
+use crate::entity::scenario;
+use crate::error::DraftError;
+use crate::format::{FormatPreset, Side};
+use crate::{Draft, Turn};
+
+#[derive(Serialize, Deserialize, Params, PartialEq, Debug)]
+struct SandboxParams {
+    scenario_id: Option<String>,
+}
+
+/// A saved scenario's id, along with the key of the browser that owns it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SavedScenario {
+    pub scenario_id: String,
+    pub owner: Uuid,
+}
+
+/// The key this browser saves its scenarios under, once the server has handed it one.
+pub(crate) fn use_scenario_owner() -> (Signal<Option<Uuid>>, WriteSignal<Option<Uuid>>) {
+    let (owner, set_owner, _) = use_local_storage::<Option<Uuid>, JsonSerdeCodec>("ScenarioOwner");
+    (owner, set_owner)
+}
+
+/// Free editing of both sides for prep. Nothing here runs on the server until the scenario is saved.
+#[component]
+pub fn Sandbox() -> impl IntoView {
+    let params = use_params::<SandboxParams>();
+    let query = use_query_map();
+
+    let scenario_id = move || {
+        params
+            .read()
+            .as_ref()
+            .ok()
+            .and_then(|params| params.scenario_id.clone())
+    };
+
+    let format = query
+        .read_untracked()
+        .get("format")
+        .and_then(|format| FormatPreset::from_str(&format).ok())
+        .unwrap_or_default();
+
+    let draft = RwSignal::new(Draft::sandbox(format.into()));
+    let name = RwSignal::new(String::new());
+    let show_error = use_error_toast();
+
+    let scenario = Resource::new(scenario_id, |scenario_id| async move {
+        match scenario_id {
+            Some(scenario_id) => load_scenario(scenario_id).await.ok(),
+            None => None,
+        }
+    });
+
+    Effect::new(move |_| {
+        let Some(Some(scenario)) = scenario.get() else { return; };
+        let Ok(state) = serde_json::from_str::<Draft>(&scenario.state) else { return; };
+        draft.set(state);
+        name.set(scenario.name);
+    });
+
+    let (owner, set_owner) = use_scenario_owner();
+    let scenarios = Resource::new(move || owner.get(), |owner| async move {
+        list_scenarios(owner.map(|owner| owner.to_string())).await.unwrap_or_default()
+    });
+
+    let saved_id = RwSignal::new(None::<String>);
+    let navigate = use_navigate();
+    Effect::new(move |_| {
+        let Some(saved_id) = saved_id.get() else { return; };
+        if scenario_id().as_ref() != Some(&saved_id) {
+            navigate(&format!("/sandbox/{saved_id}"), Default::default());
+        }
+    });
+
+    let save = move |_| {
+        let value = draft.get_untracked();
+        let state = serde_json::to_string(&value).unwrap_or_default();
+        let (scenario_id, name) = (scenario_id(), name.get_untracked());
+        let owner = owner.get_untracked().map(|owner| owner.to_string());
+        spawn_local(async move {
+            match save_scenario(scenario_id, owner, name, state).await {
+                Ok(saved) => {
+                    set_owner.set(Some(saved.owner));
+                    saved_id.set(Some(saved.scenario_id));
+                }
+                Err(err) => show_error(Err(err)),
+            }
+        });
+    };
+
+    let load = use_navigate();
+
+    view! {
+        <Grid cols=4>
+            <SideColumn
+                side=Side::Blue
+                draft=draft
+                active=Signal::derive(move || draft.get().turn.is_blue())
+                on_slot=move |turn: Turn| draft.update(|draft| draft.focus(turn))
+            />
+            <GridItem column=2 class="max-h-screen overflow-hidden">
+                <ChampionGrid
+                    draft=draft
+                    on_select=move |champion_id: u32| draft.update(|draft| {
+                        if !draft.is_champ_chosen(champion_id, None) {
+                            draft.set_slot(Some(champion_id));
+                        }
+                    })
+                />
+                <Flex justify=FlexJustify::SpaceEvenly align=FlexAlign::Center class="!h-[5%]">
+                    <select
+                        class="bg-transparent"
+                        on:change=move |event| load(&format!("/sandbox/{}", event_target_value(&event)), Default::default())
+                    >
+                        <option value="" disabled=true selected=true>"Load Scenario"</option>
+                        {
+                            move || scenarios.get().unwrap_or_default().into_iter().map(|(scenario_id, name)| view! {
+                                <option value=scenario_id>{name}</option>
+                            }).collect_view()
+                        }
+                    </select>
+                    <Input value=name placeholder="Scenario Name"/>
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        disabled=Signal::derive(move || name.get().trim().is_empty())
+                        on:click=save
+                        size=ButtonSize::Large
+                    >"Save"</Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        disabled=Signal::derive(move || draft.get().current_pick().is_none())
+                        on:click=move |_| draft.update(|draft| draft.set_slot(None))
+                        size=ButtonSize::Large
+                    >"Clear Slot"</Button>
+                </Flex>
+            </GridItem>
+            <SideColumn
+                side=Side::Red
+                draft=draft
+                active=Signal::derive(move || draft.get().turn.is_red())
+                on_slot=move |turn: Turn| draft.update(|draft| draft.focus(turn))
+            />
+        </Grid>
+    }
+}
+
+/// Saves a sandbox as a named scenario. A scenario `owner` saved before is updated in place, anything else is
+/// saved as a new scenario. A browser without an owner key yet is given one.
+#[server]
+pub(crate) async fn save_scenario(scenario_id: Option<String>, owner: Option<String>, name: String, state: String) -> Result<SavedScenario, ServerFnError<DraftError>> {
+    use chrono::Local;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let db = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.db;
+    let draft = serde_json::from_str::<Draft>(&state).map_err(|_| DraftError::InvalidScenario)?;
+    let owner = owner.and_then(|owner| Uuid::from_str(&owner).ok()).unwrap_or_else(Uuid::new_v4);
+
+    let existing = match scenario_id {
+        Some(scenario_id) => scenario::Entity::find_by_id(scenario_id).one(&db).await.map_err(DraftError::from)?
+            .filter(|scenario| scenario.owner == owner.to_string()),
+        None => None,
+    };
+    let scenario_id = existing.as_ref().map(|scenario| scenario.scenario_id.clone()).unwrap_or_else(|| Uuid::new_v4().to_string());
+    let scenario = scenario::ActiveModel {
+        scenario_id: Set(scenario_id.clone()),
+        name: Set(name.trim().to_string()),
+        format: Set(draft.format.preset.to_string()),
+        state: Set(state),
+        date_saved: Set(Local::now().naive_local()),
+        parent_draft_id: Set(draft.parent_id.map(|parent_id| parent_id.to_string())),
+        branch_step: Set(draft.branch_step.map(|step| step as u32)),
+        owner: Set(owner.to_string()),
+    };
+    match existing {
+        Some(_) => scenario.update(&db).await.map_err(DraftError::from)?,
+        None => scenario.insert(&db).await.map_err(DraftError::from)?,
+    };
+
+    Ok(SavedScenario { scenario_id, owner })
+}
+
+#[server]
+async fn load_scenario(scenario_id: String) -> Result<scenario::Model, ServerFnError<DraftError>> {
+    use sea_orm::EntityTrait;
+
+    let db = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.db;
+    let scenario = scenario::Entity::find_by_id(scenario_id).one(&db).await.map_err(DraftError::from)?;
+    Ok(scenario.ok_or(DraftError::ScenarioNotFound)?)
+}
+
+/// The id and name of every scenario `owner` has saved, most recently saved first.
+#[server]
+async fn list_scenarios(owner: Option<String>) -> Result<Vec<(String, String)>, ServerFnError<DraftError>> {
+    use sea_orm::*;
+
+    let db = use_context::<crate::AppState>().ok_or(DraftError::DatabaseMissing)?.db;
+    let Some(owner) = owner.and_then(|owner| Uuid::from_str(&owner).ok()) else { return Ok(vec![]); };
+    let scenarios = scenario::Entity::find()
+        .filter(scenario::Column::Owner.eq(owner.to_string()))
+        .order_by_desc(scenario::Column::DateSaved)
+        .all(&db)
+        .await
+        .map_err(DraftError::from)?;
+    Ok(scenarios.into_iter().map(|scenario| (scenario.scenario_id, scenario.name)).collect())
+}
@@ -0,0 +1,72 @@
+use leptos::prelude::*;
+use thaw::*;
+use crate::format::{Action, Side};
+use crate::{Draft, Turn};
+
+#[component]
+pub fn SideColumn(
+    side: Side,
+    #[prop(into)] draft: Signal<Draft>,
+    #[prop(into)] active: Signal<bool>,
+    /// Called with a slot's turn when it's clicked, for views where slots can be edited directly.
+    #[prop(optional, into)] on_slot: Option<Callback<Turn>>,
+) -> impl IntoView {
+    let (column_class, video_class) = match side {
+        Side::Blue => ("max-h-screen overflow-hidden flex flex-col items-center blueborders", "rotate-180 h-4"),
+        Side::Red => ("max-h-screen overflow-hidden flex flex-col items-center redborders", "red h-4"),
+    };
+
+    let layout = Memo::new(move |_| draft.with(|draft| draft.format.layout(side)));
+    let team = Memo::new(move |_| draft.with(|draft| draft.team(side).clone()));
+    let team_label = move || {
+        let team = team.get();
+        let name = draft.with(|draft| draft.team_name(side));
+        match team.tag.as_str() {
+            "" => name,
+            tag => format!("[{tag}] {name}"),
+        }
+    };
+
+    view! {
+        <GridItem class=column_class>
+            <video autoplay loop muted class=video_class class:transparent=move || !active.get()>
+                <source src="https://raw.communitydragon.org/pbe/plugins/rcp-fe-lol-static-assets/global/default/videos/long-progress-bar-main-loop.webm" type="video/webm"/>
+            </video>
+            <div class="flex items-center justify-center gap-2 h-[4%] w-full truncate">
+                {
+                    move || team.get().logo.map(|logo| view! {
+                        <img src=logo class="h-full aspect-square object-contain"/>
+                    })
+                }
+                <b class="truncate">{ team_label }</b>
+            </div>
+            {
+                move || layout.get().into_iter().enumerate().map(|(phase, (action, turns))| {
+                    let slots = turns.into_iter().map(|turn| {
+                        let selected = move || draft.get().acting_turns().contains(&turn);
+                        let empty = move || side.is_blue() && draft.get().get_pick(&turn).is_none();
+                        let auto_filled = move || draft.get().is_auto_filled(&turn);
+                        let suggested = move || draft.with(|draft| draft.get_pick(&turn).is_none() && draft.suggestion(&turn).is_some());
+                        let on_click = move |_| if let Some(on_slot) = on_slot { on_slot.run(turn); };
+                        let src = MaybeProp::derive(move || Some(draft.get().get_pick_image(turn)));
+                        match action {
+                            Action::Ban => view! {
+                                <Image on:click=on_click class="w-fit no-drag aspect-square" class:selected=selected class:blue=empty class:auto-filled=auto_filled src fit=ImageFit::Fill shape=ImageShape::Rounded/>
+                            }.into_any(),
+                            Action::Pick => view! {
+                                <Image on:click=on_click class="mt-1 mb-1 w-fit no-drag h-[16.5%] aspect-video" class:selected=selected class:blue=empty class:auto-filled=auto_filled class:suggested=suggested src fit=ImageFit::Fill shape=ImageShape::Circular/>
+                            }.into_any(),
+                        }
+                    }).collect_view();
+
+                    match action {
+                        Action::Ban => view! {
+                            <div class=if phase == 0 { "justify-evenly h-[5%] flex mt-1" } else { "justify-evenly h-[5%] flex" }>{slots}</div>
+                        }.into_any(),
+                        Action::Pick => slots.into_any(),
+                    }
+                }).collect_view()
+            }
+        </GridItem>
+    }
+}
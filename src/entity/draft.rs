@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 pub struct Model {
     #[cfg_attr(feature = "ssr", sea_orm(primary_key))]
     pub draft_id: String,
+    pub format: String,
     
     pub blue_ban_1: Option<u32>,
     pub blue_ban_2: Option<u32>,
@@ -37,6 +38,33 @@ pub struct Model {
     pub red_pick_5: Option<u32>,
 
     pub date_completed: chrono::NaiveDateTime,
+
+    pub series_id: Option<String>,
+    pub game_number: Option<u32>,
+    pub blue_team: Option<String>,
+    pub red_team: Option<String>,
+    pub winner: Option<String>,
+    /// Seconds left in each team's time bank, when the format had one.
+    pub blue_bank: Option<i32>,
+    pub red_bank: Option<i32>,
+    /// Comma separated steps that timed out and were filled by the timeout policy.
+    pub auto_filled: Option<String>,
+    /// The draft this one was forked from and the step it was forked at.
+    pub parent_draft_id: Option<String>,
+    pub branch_step: Option<u32>,
+    pub blue_tag: Option<String>,
+    pub red_tag: Option<String>,
+    /// Image URLs of the teams' logos.
+    pub blue_logo: Option<String>,
+    pub red_logo: Option<String>,
+    /// The settings the draft ran with on top of its preset: the turn timer as JSON, the timeout policy for
+    /// bans and picks, who saw hovers, and the comma separated champions left out of the draft. Drafts stored before
+    /// these were kept use the preset's.
+    pub timer: Option<String>,
+    pub ban_timeout: Option<String>,
+    pub pick_timeout: Option<String>,
+    pub hover_visibility: Option<String>,
+    pub disabled_champions: Option<String>,
 }
 
 use cfg_if::cfg_if;
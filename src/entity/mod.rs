@@ -8,4 +8,8 @@ cfg_if! {
 }
 
 pub mod champion;
-pub mod draft;
\ No newline at end of file
+pub mod draft;
+pub mod draft_event;
+pub mod live_draft;
+pub mod scenario;
+pub mod series;
\ No newline at end of file
@@ -2,3 +2,7 @@
 
 pub use super::champion::Entity as Champion;
 pub use super::draft::Entity as Draft;
+pub use super::draft_event::Entity as DraftEvent;
+pub use super::live_draft::Entity as LiveDraft;
+pub use super::scenario::Entity as Scenario;
+pub use super::series::Entity as Series;
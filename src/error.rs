@@ -0,0 +1,91 @@
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+use thiserror::Error;
+
+/// Everything a draft server function can fail with. It crosses the wire as its message, so `FromStr`
+/// parses back exactly what `Display` writes.
+#[derive(Error, EnumIter, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DraftError {
+    #[error("Draft not found.")]
+    DraftNotFound,
+    #[error("The draft is still in progress.")]
+    DraftInProgress,
+    #[error("Invalid team id.")]
+    InvalidTeam,
+    #[error("This link isn't for a team in this draft.")]
+    NotInDraft,
+    #[error("This link isn't the draft's referee link.")]
+    NotReferee,
+    #[error("It isn't your turn.")]
+    NotYourTurn,
+    #[error("The draft has moved on since that was sent.")]
+    StaleTurn,
+    #[error("Another browser has already claimed this team's seat.")]
+    SeatClaimed,
+    #[error("Only the captain or the player whose pick it is can do that.")]
+    NotYourPick,
+    #[error("Only the captain can do that.")]
+    NotCaptain,
+    #[error("Join the team before suggesting a champion.")]
+    NotJoined,
+    #[error("Turn timers must be between a second and an hour, and time banks at most an hour.")]
+    InvalidTimer,
+    #[error("Champion does not exist.")]
+    UnknownChampion,
+    #[error("Champion is not available.")]
+    ChampionUnavailable,
+    #[error("Hover a champion before locking in.")]
+    NothingHovered,
+    #[error("The game result can't be reported.")]
+    ResultUnavailable,
+    #[error("Only the losing team can choose a side.")]
+    NotLoser,
+    #[error("There is no next game to choose a side for.")]
+    NoNextGame,
+    #[error("This game's series has already moved on to a later game.")]
+    SeriesMovedOn,
+    #[error("The draft is paused.")]
+    Paused,
+    #[error("The draft isn't paused.")]
+    NotPaused,
+    #[error("Your team has no pauses left.")]
+    NoPausesLeft,
+    #[error("Only whoever paused the draft can resume it.")]
+    NotYourPause,
+    #[error("There is no locked turn to undo.")]
+    NothingToUndo,
+    #[error("Only the team that locked the last turn can undo it.")]
+    NotYourUndo,
+    #[error("The other team has to approve the undo.")]
+    OwnUndo,
+    #[error("The undo request has expired.")]
+    UndoExpired,
+    #[error("Scenario not found.")]
+    ScenarioNotFound,
+    #[error("Scenario could not be read.")]
+    InvalidScenario,
+    #[error("Database connection missing.")]
+    DatabaseMissing,
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl FromStr for DraftError {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(message) = s.strip_prefix("Database error: ") {
+            return Ok(DraftError::Database(message.to_string()));
+        }
+        DraftError::iter().find(|error| error.to_string() == s).ok_or(())
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<sea_orm::DbErr> for DraftError {
+    fn from(value: sea_orm::DbErr) -> Self {
+        DraftError::Database(value.to_string())
+    }
+}
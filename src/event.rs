@@ -0,0 +1,74 @@
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIs, EnumString};
+use crate::entity::draft_event;
+use crate::format::Side;
+
+#[derive(Display, EnumIs, EnumString, PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum EventKind {
+    Ready,
+    Hover,
+    Lock,
+    Timeout,
+    Pause,
+    Resume,
+    /// The last locked turn was taken back, with the other team's or a referee's approval.
+    Revert,
+    /// A referee filled or cleared a slot directly.
+    Set,
+    /// A referee sent the draft back to `PreDraft`.
+    Reset,
+    /// A referee cancelled the draft.
+    Cancel,
+    /// A referee unseated a team's players.
+    Release,
+    /// A referee replaced a team's link.
+    Relink,
+    /// Both teams picked the same champion in a blind phase, so the later pick was sent back.
+    Clash,
+    /// The game's winner was decided, by the teams agreeing or by a referee.
+    Winner,
+}
+
+/// Something that happened during a draft. A draft's events are only ever appended to.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct DraftEvent {
+    pub kind: EventKind,
+    pub side: Option<Side>,
+    pub step: Option<usize>,
+    pub champion: Option<u32>,
+    pub at: DateTime<Utc>,
+    #[serde(default)]
+    pub referee: bool,
+}
+
+impl DraftEvent {
+    pub fn to_model(&self, draft_id: String, sequence: u32) -> draft_event::Model {
+        draft_event::Model {
+            draft_id,
+            sequence,
+            kind: self.kind.to_string(),
+            side: self.side.map(|side| side.to_string()),
+            step: self.step.map(|step| step as u32),
+            champion: self.champion,
+            created_at: self.at.naive_utc(),
+            referee: self.referee,
+        }
+    }
+}
+
+impl TryFrom<draft_event::Model> for DraftEvent {
+    type Error = strum::ParseError;
+
+    fn try_from(value: draft_event::Model) -> Result<Self, Self::Error> {
+        Ok(DraftEvent {
+            kind: EventKind::from_str(&value.kind)?,
+            side: value.side.and_then(|side| Side::from_str(&side).ok()),
+            step: value.step.map(|step| step as usize),
+            champion: value.champion,
+            at: value.created_at.and_utc(),
+            referee: value.referee,
+        })
+    }
+}
@@ -0,0 +1,271 @@
+use std::ops::Range;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIs, EnumIter, EnumString};
+use crate::Turn;
+
+/// Persistence stores five ban and five pick columns per side, so no format may exceed that.
+pub const MAX_SLOTS: usize = 5;
+
+#[derive(Display, EnumIs, EnumString, PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Side {
+    Blue,
+    Red,
+}
+
+impl Side {
+    pub fn opponent(&self) -> Side {
+        match self {
+            Side::Blue => Side::Red,
+            Side::Red => Side::Blue,
+        }
+    }
+}
+
+#[derive(Display, EnumIs, PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Action {
+    Ban,
+    Pick,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct DraftStep {
+    pub side: Side,
+    pub action: Action,
+    /// Taken at the same time as the step before it. Steps taken together form a blind phase, where both teams
+    /// act at once and only see each other's choices once the phase ends.
+    #[serde(default)]
+    pub with_previous: bool,
+}
+
+#[derive(Default, Display, EnumIter, EnumString, PartialEq, Eq, EnumIs, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum FormatPreset {
+    #[default]
+    Tournament,
+    Ranked,
+    ThreeBan,
+}
+
+/// Turn lengths for a format. The server lets a turn run `grace_seconds` past the deadline clients count
+/// down to, so a confirm sent at zero still lands. Once a turn's own time runs out the acting team's time
+/// bank drains, like a chess clock.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct TurnTimer {
+    pub ban_seconds: i64,
+    pub pick_seconds: i64,
+    /// How long the other team has to ready up once one team is ready, after which the draft starts anyway.
+    pub ready_seconds: Option<i64>,
+    pub bank_seconds: i64,
+    pub grace_seconds: i64,
+    /// Tech pauses each team may call. Pausing freezes every clock until the draft is resumed.
+    #[serde(default)]
+    pub pauses: u32,
+}
+
+impl Default for TurnTimer {
+    fn default() -> Self {
+        TurnTimer {
+            ban_seconds: 30,
+            pick_seconds: 30,
+            ready_seconds: None,
+            bank_seconds: 0,
+            grace_seconds: 3,
+            pauses: 2,
+        }
+    }
+}
+
+/// The longest a turn, the ready timeout or a time bank may be set to.
+pub const MAX_TIMER_SECONDS: i64 = 60 * 60;
+
+impl TurnTimer {
+    /// The timer a draft's creator asked for, if every length is in range. Turns and the ready timeout take at
+    /// least a second and banks may be empty, but none run past `MAX_TIMER_SECONDS`. The grace period isn't
+    /// the creator's to choose, so it's taken from `preset`.
+    pub fn checked(self, preset: TurnTimer) -> Option<TurnTimer> {
+        let in_range = |seconds: i64, min: i64| (min..=MAX_TIMER_SECONDS).contains(&seconds);
+        let valid = in_range(self.ban_seconds, 1) &&
+            in_range(self.pick_seconds, 1) &&
+            in_range(self.bank_seconds, 0) &&
+            self.ready_seconds.is_none_or(|seconds| in_range(seconds, 1));
+        valid.then_some(TurnTimer { grace_seconds: preset.grace_seconds, ..self })
+    }
+
+    pub fn turn_seconds(&self, action: Action) -> i64 {
+        match action {
+            Action::Ban => self.ban_seconds,
+            Action::Pick => self.pick_seconds,
+        }
+    }
+}
+
+#[derive(Default, Display, EnumIter, EnumString, PartialEq, Eq, EnumIs, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum BanTimeout {
+    /// Locks whatever is hovered, leaving the ban empty if nothing is.
+    #[default]
+    LockHover,
+    Skip,
+}
+
+#[derive(Default, Display, EnumIter, EnumString, PartialEq, Eq, EnumIs, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum PickTimeout {
+    /// Locks whatever is hovered, or a random champion if nothing is.
+    #[default]
+    LockHover,
+    Random,
+    /// Locks the team's highest priority champion that's still available, or a random one if none are.
+    Priority,
+}
+
+/// Who sees a team's hover before it's locked in. The team itself and the referee always do.
+#[derive(Default, Display, EnumIter, EnumString, PartialEq, Eq, EnumIs, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum HoverVisibility {
+    #[default]
+    VisibleToAll,
+    /// The caster link still sees it, so casters can talk over the hover. The public spectator link doesn't,
+    /// since the opponent could open it.
+    HiddenFromOpponent,
+    HiddenUntilLock,
+}
+
+/// What happens to the slot of a turn that runs out of time.
+#[derive(Default, PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct TimeoutPolicy {
+    pub ban: BanTimeout,
+    pub pick: PickTimeout,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct DraftFormat {
+    pub preset: FormatPreset,
+    pub steps: Vec<DraftStep>,
+    pub ban_count: usize,
+    pub pick_count: usize,
+    #[serde(default)]
+    pub timer: TurnTimer,
+    #[serde(default)]
+    pub timeout: TimeoutPolicy,
+}
+
+impl Default for DraftFormat {
+    fn default() -> Self {
+        FormatPreset::default().into()
+    }
+}
+
+impl From<FormatPreset> for DraftFormat {
+    fn from(preset: FormatPreset) -> Self {
+        use Action::*;
+        use Side::*;
+
+        let timer = match preset {
+            FormatPreset::Tournament | FormatPreset::ThreeBan => TurnTimer::default(),
+            // Solo queue doesn't wait on anyone. Its ban length covers the whole blind ban phase.
+            FormatPreset::Ranked => TurnTimer {
+                ready_seconds: Some(30),
+                ..Default::default()
+            },
+        };
+
+        let (order, ban_count, pick_count) = match preset {
+            // Pro play: three bans each, three picks, two more bans each, two more picks.
+            FormatPreset::Tournament => (vec![
+                (Blue, Ban), (Red, Ban), (Blue, Ban), (Red, Ban), (Blue, Ban), (Red, Ban),
+                (Blue, Pick), (Red, Pick), (Red, Pick), (Blue, Pick), (Blue, Pick), (Red, Pick),
+                (Red, Ban), (Blue, Ban), (Red, Ban), (Blue, Ban),
+                (Red, Pick), (Blue, Pick), (Blue, Pick), (Red, Pick),
+            ], 5, 5),
+            // Solo queue: five bans a team, all taken at once, then a snake pick.
+            FormatPreset::Ranked => (vec![
+                (Blue, Ban), (Red, Ban), (Blue, Ban), (Red, Ban), (Blue, Ban),
+                (Red, Ban), (Blue, Ban), (Red, Ban), (Blue, Ban), (Red, Ban),
+                (Blue, Pick), (Red, Pick), (Red, Pick), (Blue, Pick), (Blue, Pick),
+                (Red, Pick), (Red, Pick), (Blue, Pick), (Blue, Pick), (Red, Pick),
+            ], 5, 5),
+            // Tournament order without the second ban phase.
+            FormatPreset::ThreeBan => (vec![
+                (Blue, Ban), (Red, Ban), (Blue, Ban), (Red, Ban), (Blue, Ban), (Red, Ban),
+                (Blue, Pick), (Red, Pick), (Red, Pick), (Blue, Pick), (Blue, Pick),
+                (Red, Pick), (Red, Pick), (Blue, Pick), (Blue, Pick), (Red, Pick),
+            ], 3, 5),
+        };
+
+        let mut steps: Vec<DraftStep> = order.into_iter().map(|(side, action)| DraftStep { side, action, with_previous: false }).collect();
+        // Solo queue bans are one blind phase, all ten taken at once.
+        if preset.is_ranked() {
+            for step in steps.iter_mut().take(10).skip(1) {
+                step.with_previous = true;
+            }
+        }
+
+        DraftFormat {
+            preset,
+            steps,
+            ban_count,
+            pick_count,
+            timer,
+            timeout: TimeoutPolicy::default(),
+        }
+    }
+}
+
+impl DraftFormat {
+    /// The slot filled by the step at `step`, or `PostDraft` once the steps run out.
+    pub fn turn(&self, step: usize) -> Turn {
+        let Some(current) = self.steps.get(step) else { return Turn::PostDraft; };
+        let index = self.steps[..step].iter().filter(|previous| previous.side == current.side && previous.action == current.action).count();
+        match current.action {
+            Action::Ban => Turn::Ban(current.side, index),
+            Action::Pick => Turn::Pick(current.side, index),
+        }
+    }
+
+    /// The steps taken together with `step`, which is just `step` itself outside of a blind phase.
+    pub fn phase(&self, step: usize) -> Range<usize> {
+        let start = (0..=step).rev().find(|start| self.steps.get(*start).is_none_or(|draft_step| !draft_step.with_previous)).unwrap_or(0);
+        let end = (step + 1..self.steps.len()).find(|end| !self.steps[*end].with_previous).unwrap_or(self.steps.len());
+        start..end.max(start + 1)
+    }
+
+    /// Whether `side` has a step in the phase `step` belongs to.
+    pub fn acts_in_phase(&self, step: usize, side: Side) -> bool {
+        self.phase(step).any(|step| self.steps.get(step).is_some_and(|draft_step| draft_step.side == side))
+    }
+
+    /// Groups a side's slots into the runs of bans and picks they're drafted in, for the side columns.
+    pub fn layout(&self, side: Side) -> Vec<(Action, Vec<Turn>)> {
+        let mut phases: Vec<(Action, Vec<Turn>)> = vec![];
+        for (step, draft_step) in self.steps.iter().enumerate() {
+            if phases.last().is_none_or(|(action, _)| *action != draft_step.action) {
+                phases.push((draft_step.action, vec![]));
+            }
+            if draft_step.side == side {
+                if let Some((_, turns)) = phases.last_mut() {
+                    turns.push(self.turn(step));
+                }
+            }
+        }
+        phases.retain(|(_, turns)| !turns.is_empty());
+        phases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranked_bans_are_one_phase() {
+        let format = DraftFormat::from(FormatPreset::Ranked);
+        assert_eq!(format.phase(0), 0..10);
+        assert_eq!(format.phase(9), 0..10);
+        assert_eq!(format.phase(10), 10..11);
+        assert!(format.acts_in_phase(4, Side::Red));
+    }
+
+    #[test]
+    fn sequential_steps_are_their_own_phase() {
+        let format = DraftFormat::from(FormatPreset::Tournament);
+        assert_eq!(format.phase(0), 0..1);
+        assert_eq!(format.phase(7), 7..8);
+    }
+}
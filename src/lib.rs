@@ -1,17 +1,30 @@
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIs;
 use uuid::Uuid;
+use crate::event::{DraftEvent, EventKind};
+use crate::format::{Action, BanTimeout, DraftFormat, HoverVisibility, PickTimeout, Side};
+use crate::series::{FearlessMode, SideSelection, TeamInfo};
 
 pub mod draft;
 pub mod app;
 pub mod entity;
 pub mod api;
+pub mod error;
+pub mod event;
+pub mod format;
+pub mod series;
+#[cfg(feature = "ssr")]
+pub mod lifecycle;
+#[cfg(feature = "ssr")]
+pub mod registry;
 
 #[cfg(feature = "ssr")]
 #[derive(Clone)]
 pub struct AppState {
     pub db: sea_orm::DatabaseConnection,
     pub server_signals: leptos_ws::server_signals::ServerSignals,
+    pub registry: registry::DraftRegistry,
 }
 
 #[cfg(feature = "hydrate")]
@@ -22,136 +35,622 @@ pub fn hydrate() {
     leptos::mount::hydrate_body(App);
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, Default, EnumIs)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, EnumIs)]
 pub enum Turn {
     #[default]
     PreDraft,
-    BlueBan1,
-    BlueBan2,
-    BlueBan3,
-    BlueBan4,
-    BlueBan5,
-    RedBan1,
-    RedBan2,
-    RedBan3,
-    RedBan4,
-    RedBan5,
-    BluePick1,
-    BluePick2,
-    BluePick3,
-    BluePick4,
-    BluePick5,
-    RedPick1,
-    RedPick2,
-    RedPick3,
-    RedPick4,
-    RedPick5,
+    Ban(Side, usize),
+    Pick(Side, usize),
     PostDraft,
 }
 
 impl Turn {
-    fn is_ban(&self) -> bool {
-        self.is_blue_ban_1() ||
-        self.is_blue_ban_2() ||
-        self.is_blue_ban_3() ||
-        self.is_blue_ban_4() ||
-        self.is_blue_ban_5() ||
-        self.is_red_ban_1() ||
-        self.is_red_ban_2() ||
-        self.is_red_ban_3() ||
-        self.is_red_ban_4() ||
-        self.is_red_ban_5()
+    fn side(&self) -> Option<Side> {
+        match self {
+            Turn::Ban(side, _) | Turn::Pick(side, _) => Some(*side),
+            _ => None,
+        }
+    }
+
+    fn action(&self) -> Option<Action> {
+        match self {
+            Turn::Ban(_, _) => Some(Action::Ban),
+            Turn::Pick(_, _) => Some(Action::Pick),
+            _ => None,
+        }
     }
 
     fn is_blue(&self) -> bool {
-        self.is_blue_ban_1() ||
-        self.is_blue_ban_2() ||
-        self.is_blue_ban_3() ||
-        self.is_blue_ban_4() ||
-        self.is_blue_ban_5() ||
-        self.is_blue_pick_1() ||
-        self.is_blue_pick_2() ||
-        self.is_blue_pick_3() ||
-        self.is_blue_pick_4() ||
-        self.is_blue_pick_5()
+        self.side().is_some_and(|side| side.is_blue())
     }
 
     fn is_red(&self) -> bool {
-        self.is_red_ban_1() ||
-        self.is_red_ban_2() ||
-        self.is_red_ban_3() ||
-        self.is_red_ban_4() ||
-        self.is_red_ban_5() ||
-        self.is_red_pick_1() ||
-        self.is_red_pick_2() ||
-        self.is_red_pick_3() ||
-        self.is_red_pick_4() ||
-        self.is_red_pick_5()
+        self.side().is_some_and(|side| side.is_red())
     }
 }
 
+/// How long the other team has to approve an undo request.
+const UNDO_WINDOW_SECONDS: i64 = 15;
+
+/// A team asking to take back the turn it last locked.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UndoRequest {
+    side: Side,
+    step: usize,
+    expires: DateTime<Utc>,
+}
+
+/// Who stopped the clocks and when. `by` is `None` when a referee paused the draft.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pause {
+    by: Option<Side>,
+    at: DateTime<Utc>,
+}
+
+/// Someone drafting under a team link. Seats line up with the team's pick slots, so the player in seat 0
+/// owns the team's first pick.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Player {
+    player_id: Uuid,
+    /// The secret the player's browser sends with every command, issued by the server when they join.
+    #[serde(default)]
+    token: Uuid,
+    name: String,
+    seat: Option<usize>,
+    captain: bool,
+    /// The champion the player would like for their own pick, shown to teammates.
+    suggestion: Option<u32>,
+}
+
+/// Who a view of the draft is published for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Viewer {
+    Spectator,
+    Caster,
+    Team(Side),
+    Referee,
+}
+
+/// What a browser keeps to act for its seat: the id teammates know it by and the token it proves itself with.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeatPass {
+    pub player_id: Uuid,
+    pub token: Uuid,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Draft {
     draft_id: Uuid,
     blue_id: Uuid,
     red_id: Uuid,
+    /// The referee link's secret, for officials running a draft on the teams' behalf.
+    #[serde(default)]
+    admin_id: Uuid,
+    /// The caster link's secret. Under `HiddenFromOpponent` it sees both teams' hovers, which the public
+    /// spectator link doesn't, since any team can open that.
+    #[serde(default)]
+    caster_id: Uuid,
 
     blue_ready: bool,
     red_ready: bool,
-    
-    blue_ban_1: Option<u32>,
-    blue_ban_2: Option<u32>,
-    blue_ban_3: Option<u32>,
-    blue_ban_4: Option<u32>,
-    blue_ban_5: Option<u32>,
-
-    red_ban_1: Option<u32>,
-    red_ban_2: Option<u32>,
-    red_ban_3: Option<u32>,
-    red_ban_4: Option<u32>,
-    red_ban_5: Option<u32>,
-    
-    blue_pick_1: Option<u32>,
-    blue_pick_2: Option<u32>,
-    blue_pick_3: Option<u32>,
-    blue_pick_4: Option<u32>,
-    blue_pick_5: Option<u32>,
-
-    red_pick_1: Option<u32>,
-    red_pick_2: Option<u32>,
-    red_pick_3: Option<u32>,
-    red_pick_4: Option<u32>,
-    red_pick_5: Option<u32>,
 
+    format: DraftFormat,
+
+    blue_bans: Vec<Option<u32>>,
+    red_bans: Vec<Option<u32>>,
+    blue_picks: Vec<Option<u32>>,
+    red_picks: Vec<Option<u32>>,
+
+    step: usize,
     turn: Turn,
+    /// Bumped on every turn change and series result, so commands sent against an older state are rejected.
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    turn_deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    ready_deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    blue_bank_millis: i64,
+    #[serde(default)]
+    red_bank_millis: i64,
+
+    #[serde(default)]
+    blue_priorities: Vec<u32>,
+    #[serde(default)]
+    red_priorities: Vec<u32>,
+    /// Steps whose slot was filled by the timeout policy rather than a confirm.
+    #[serde(default)]
+    auto_filled: Vec<usize>,
+    /// Steps of the current blind phase whose team has already locked in.
+    #[serde(default)]
+    locked_steps: Vec<usize>,
+    #[serde(default)]
+    events: Vec<DraftEvent>,
+    #[serde(default)]
+    undo_request: Option<UndoRequest>,
+    #[serde(default)]
+    blue_players: Vec<Player>,
+    #[serde(default)]
+    red_players: Vec<Player>,
+    /// A claimed team's roster is locked, so only the players already seated can act for it.
+    #[serde(default)]
+    blue_claimed: bool,
+    #[serde(default)]
+    red_claimed: bool,
+
+    #[serde(default)]
+    hover_visibility: HoverVisibility,
+    /// Champions the organizer took out of the pool for the whole draft.
+    #[serde(default)]
+    disabled_champions: Vec<u32>,
+    #[serde(default)]
+    paused: Option<Pause>,
+    #[serde(default)]
+    blue_pauses_used: u32,
+    #[serde(default)]
+    red_pauses_used: u32,
+
+    blue_team: TeamInfo,
+    red_team: TeamInfo,
+
+    series_id: Option<Uuid>,
+    game_number: u32,
+    best_of: Option<u32>,
+    side_selection: SideSelection,
+    fearless: FearlessMode,
+    blue_series_picks: Vec<u32>,
+    red_series_picks: Vec<u32>,
+    blue_series_wins: u32,
+    red_series_wins: u32,
+    winner: Option<Side>,
+    /// The winner each team has reported. A result only stands once both agree, unless the referee sets it.
+    #[serde(default)]
+    blue_report: Option<Side>,
+    #[serde(default)]
+    red_report: Option<Side>,
+    next_draft_id: Option<Uuid>,
+
+    /// The completed draft this one was branched from, and the step it was branched at.
+    #[serde(default)]
+    parent_id: Option<Uuid>,
+    #[serde(default)]
+    branch_step: Option<usize>,
+
+    saved: bool,
+    /// Set when a saved game waited too long on its result or next game, which lets it go without them.
+    #[serde(default)]
+    closed: bool,
 }
 
 impl Draft {
-    fn is_champ_chosen(&self, id: u32) -> bool {
-        self.blue_ban_1.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_ban_1() ||
-        self.blue_ban_2.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_ban_2() ||
-        self.blue_ban_3.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_ban_3() ||
-        self.blue_ban_4.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_ban_4() ||
-        self.blue_ban_5.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_ban_5() ||
-        self.blue_pick_1.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_pick_1() ||
-        self.blue_pick_2.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_pick_2() ||
-        self.blue_pick_3.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_pick_3() ||
-        self.blue_pick_4.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_pick_4() ||
-        self.blue_pick_5.is_some_and(|selection_id| selection_id == id) && !self.turn.is_blue_pick_5() ||
-        self.red_ban_1.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_ban_1() ||
-        self.red_ban_2.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_ban_2() ||
-        self.red_ban_3.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_ban_3() ||
-        self.red_ban_4.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_ban_4() ||
-        self.red_ban_5.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_ban_5() ||
-        self.red_pick_1.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_pick_1() ||
-        self.red_pick_2.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_pick_2() ||
-        self.red_pick_3.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_pick_3() ||
-        self.red_pick_4.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_pick_4() ||
-        self.red_pick_5.is_some_and(|selection_id| selection_id == id) && !self.turn.is_red_pick_5()
+    #[allow(dead_code)]
+    fn new(draft_id: Uuid, blue_id: Uuid, red_id: Uuid, format: DraftFormat) -> Self {
+        Draft {
+            draft_id,
+            blue_id,
+            red_id,
+            blue_bans: vec![None; format.ban_count],
+            red_bans: vec![None; format.ban_count],
+            blue_picks: vec![None; format.pick_count],
+            red_picks: vec![None; format.pick_count],
+            blue_bank_millis: format.timer.bank_seconds * 1000,
+            red_bank_millis: format.timer.bank_seconds * 1000,
+            format,
+            ..Default::default()
+        }
+    }
+
+    /// Links the draft to a series, carrying over each team's picks and wins from the games already played in it.
+    #[allow(dead_code)]
+    fn join_series(&mut self, series_id: Uuid, fearless: FearlessMode, games: Vec<Draft>) {
+        self.series_id = Some(series_id);
+        self.game_number = games.len() as u32 + 1;
+        self.fearless = fearless;
+        for game in games {
+            for side in [Side::Blue, Side::Red] {
+                let current_side = self.side_of(game.team(side), side);
+                let picks: Vec<u32> = game.slots(side, Action::Pick).iter().flatten().copied().collect();
+                match current_side {
+                    Side::Blue => self.blue_series_picks.extend(picks),
+                    Side::Red => self.red_series_picks.extend(picks),
+                }
+                if game.winner == Some(side) {
+                    match current_side {
+                        Side::Blue => self.blue_series_wins += 1,
+                        Side::Red => self.red_series_wins += 1,
+                    }
+                }
+            }
+        }
+    }
+
+    /// The next game of the series with the same team links, optionally swapping sides.
+    #[allow(dead_code)]
+    fn next_game(&self, draft_id: Uuid, swap_sides: bool) -> Draft {
+        let (blue_id, red_id, blue_team, red_team) = match swap_sides {
+            true => (self.red_id, self.blue_id, self.red_team.clone(), self.blue_team.clone()),
+            false => (self.blue_id, self.red_id, self.blue_team.clone(), self.red_team.clone()),
+        };
+        let mut draft = Draft::new(draft_id, blue_id, red_id, self.format.clone());
+        draft.blue_team = blue_team;
+        draft.red_team = red_team;
+        draft.admin_id = self.admin_id;
+        draft.caster_id = self.caster_id;
+        draft.hover_visibility = self.hover_visibility;
+        draft.disabled_champions = self.disabled_champions.clone();
+        draft.best_of = self.best_of;
+        draft.side_selection = self.side_selection;
+        draft
+    }
+
+    /// A "what-if" draft branched from this one at `step`. The slots before it are kept and the rest are open.
+    /// A step in the middle of a blind phase branches from the start of that phase.
+    #[allow(dead_code)]
+    fn fork(&self, draft_id: Uuid, blue_id: Uuid, red_id: Uuid, step: usize) -> Draft {
+        let step = self.format.phase(step.min(self.format.steps.len())).start;
+        let mut draft = Draft::new(draft_id, blue_id, red_id, self.format.clone());
+        for previous in 0..step {
+            let turn = self.format.turn(previous);
+            let pick = self.get_pick(&turn);
+            if let Some(slot) = draft.slot_mut(&turn) {
+                *slot = pick;
+            }
+        }
+        draft.blue_team = self.blue_team.clone();
+        draft.red_team = self.red_team.clone();
+        draft.hover_visibility = self.hover_visibility;
+        draft.disabled_champions = self.disabled_champions.clone();
+        draft.parent_id = Some(self.draft_id);
+        draft.branch_step = Some(step);
+        draft
+    }
+
+    fn team_side(&self, team_id: &Uuid) -> Option<Side> {
+        match *team_id {
+            team_id if team_id == self.blue_id => Some(Side::Blue),
+            team_id if team_id == self.red_id => Some(Side::Red),
+            _ => None,
+        }
+    }
+
+    fn team(&self, side: Side) -> &TeamInfo {
+        match side {
+            Side::Blue => &self.blue_team,
+            Side::Red => &self.red_team,
+        }
+    }
+
+    fn team_name(&self, side: Side) -> String {
+        match self.team(side).name.as_str() {
+            "" => side.to_string(),
+            name => name.to_string(),
+        }
+    }
+
+    fn players(&self, side: Side) -> &Vec<Player> {
+        match side {
+            Side::Blue => &self.blue_players,
+            Side::Red => &self.red_players,
+        }
+    }
+
+    fn players_mut(&mut self, side: Side) -> &mut Vec<Player> {
+        match side {
+            Side::Blue => &mut self.blue_players,
+            Side::Red => &mut self.red_players,
+        }
+    }
+
+    fn player(&self, side: Side, token: Option<&Uuid>) -> Option<&Player> {
+        self.players(side).iter().find(|player| Some(&player.token) == token)
+    }
+
+    fn is_claimed(&self, side: Side) -> bool {
+        match side {
+            Side::Blue => self.blue_claimed,
+            Side::Red => self.red_claimed,
+        }
+    }
+
+    fn is_ready(&self, side: Side) -> bool {
+        match side {
+            Side::Blue => self.blue_ready,
+            Side::Red => self.red_ready,
+        }
+    }
+
+    #[allow(dead_code)]
+    fn claim(&mut self, side: Side) {
+        match side {
+            Side::Blue => self.blue_claimed = true,
+            Side::Red => self.red_claimed = true,
+        }
+    }
+
+    /// Unseats every player of a team and unlocks its roster, for when a team has lost its browser.
+    #[allow(dead_code)]
+    fn release(&mut self, side: Side) {
+        self.players_mut(side).clear();
+        match side {
+            Side::Blue => self.blue_claimed = false,
+            Side::Red => self.red_claimed = false,
+        }
+        self.record(EventKind::Release, Some(side), None);
+    }
+
+    /// Replaces a team's link, so the old one only shows the draft as a spectator.
+    #[allow(dead_code)]
+    fn relink(&mut self, side: Side, team_id: Uuid) {
+        match side {
+            Side::Blue => self.blue_id = team_id,
+            Side::Red => self.red_id = team_id,
+        }
+        self.record(EventKind::Relink, Some(side), None);
+    }
+
+    /// Seats a player under a team link, or renames them if `pass` already holds a seat. The first player to
+    /// join is the captain, and players past the team's pick count join without a seat.
+    #[allow(dead_code)]
+    fn join(&mut self, side: Side, pass: SeatPass, name: String) {
+        if let Some(player) = self.players_mut(side).iter_mut().find(|player| player.token == pass.token) {
+            player.name = name;
+            return;
+        }
+
+        let players = self.players(side);
+        let seat = (0..self.format.pick_count).find(|seat| !players.iter().any(|player| player.seat == Some(*seat)));
+        let captain = players.is_empty();
+        let player = Player { player_id: pass.player_id, token: pass.token, name, seat, captain, suggestion: None };
+        self.players_mut(side).push(player);
     }
 
+    /// Hands the side's captaincy to one of its players. Returns false, and leaves the captain as is, if the
+    /// player isn't on that side.
+    #[allow(dead_code)]
+    fn make_captain(&mut self, side: Side, player_id: &Uuid) -> bool {
+        if !self.players(side).iter().any(|player| player.player_id == *player_id) {
+            return false;
+        }
+        for player in self.players_mut(side).iter_mut() {
+            player.captain = player.player_id == *player_id;
+        }
+        true
+    }
+
+    #[allow(dead_code)]
+    fn suggest(&mut self, side: Side, token: &Uuid, champion: Option<u32>) {
+        if let Some(player) = self.players_mut(side).iter_mut().find(|player| player.token == *token) {
+            player.suggestion = champion;
+        }
+    }
+
+    fn is_captain(&self, side: Side, token: Option<&Uuid>) -> bool {
+        self.player(side, token).is_some_and(|player| player.captain)
+    }
+
+    /// Whether a player may hover and confirm for their team on the current turn: the captain always can, other
+    /// players only on their own pick. Teams nobody has joined act through the team link alone.
+    fn can_act(&self, side: Side, token: Option<&Uuid>) -> bool {
+        self.can_player_act(side, self.player(side, token))
+    }
+
+    fn can_player_act(&self, side: Side, player: Option<&Player>) -> bool {
+        if self.players(side).is_empty() {
+            return true;
+        }
+        let Some(player) = player else { return false; };
+        player.captain || matches!(self.side_turn(side), Some(Turn::Pick(_, index)) if player.seat == Some(index))
+    }
+
+    /// The suggestion of the player seated at a pick slot.
+    fn suggestion(&self, turn: &Turn) -> Option<u32> {
+        let Turn::Pick(side, index) = turn else { return None; };
+        self.players(*side).iter().find(|player| player.seat == Some(*index)).and_then(|player| player.suggestion)
+    }
+
+    /// Updates a team's details. Names are fixed after a series' first game, since later games find each team by name.
+    #[allow(dead_code)]
+    fn set_team(&mut self, side: Side, team: TeamInfo) {
+        let current = match side {
+            Side::Blue => &mut self.blue_team,
+            Side::Red => &mut self.red_team,
+        };
+        if self.series_id.is_none() || self.game_number <= 1 {
+            current.name = team.name;
+        }
+        current.tag = team.tag;
+        current.logo = team.logo;
+    }
+
+    /// The side a team from an earlier game is on in this one. Teams are matched by name, unnamed teams keep their side.
+    fn side_of(&self, team: &TeamInfo, side: Side) -> Side {
+        match team {
+            team if team.name.is_empty() => side,
+            team if team.name == self.blue_team.name => Side::Blue,
+            team if team.name == self.red_team.name => Side::Red,
+            _ => side,
+        }
+    }
+
+    fn series_picks(&self, side: Side) -> &Vec<u32> {
+        match side {
+            Side::Blue => &self.blue_series_picks,
+            Side::Red => &self.red_series_picks,
+        }
+    }
+
+    /// Series wins of the team on `side`, including this game once its winner is reported.
+    fn series_wins(&self, side: Side) -> u32 {
+        let wins = match side {
+            Side::Blue => self.blue_series_wins,
+            Side::Red => self.red_series_wins,
+        };
+        wins + u32::from(self.winner == Some(side))
+    }
+
+    /// Whether a best-of series is still waiting on this game's result or on further games.
+    fn series_in_progress(&self) -> bool {
+        let Some(best_of) = self.best_of else { return false; };
+        let needed = best_of / 2 + 1;
+        self.winner.is_none() ||
+        self.series_wins(Side::Blue) < needed && self.series_wins(Side::Red) < needed && self.game_number < best_of
+    }
+
+    fn report(&self, side: Side) -> Option<Side> {
+        match side {
+            Side::Blue => self.blue_report,
+            Side::Red => self.red_report,
+        }
+    }
+
+    /// Records the winner a team reports, deciding the game once both teams report the same one.
+    #[allow(dead_code)]
+    fn report_winner(&mut self, side: Side, winner: Side) {
+        match side {
+            Side::Blue => self.blue_report = Some(winner),
+            Side::Red => self.red_report = Some(winner),
+        }
+        if self.blue_report.is_some() && self.blue_report == self.red_report {
+            self.set_winner(winner);
+        } else {
+            self.version += 1;
+        }
+    }
+
+    /// Sets or corrects the game's winner, whatever the teams reported.
+    #[allow(dead_code)]
+    fn set_winner(&mut self, winner: Side) {
+        self.winner = Some(winner);
+        self.version += 1;
+        self.record(EventKind::Winner, Some(winner), None);
+    }
+
+    /// Whether nothing is left to do with the live draft: it's saved, and any series it's part of has either been
+    /// decided, moved on to its next game, or been given up on.
+    #[allow(dead_code)]
+    fn is_finished(&self) -> bool {
+        self.saved && (self.closed || !self.series_in_progress() || self.next_draft_id.is_some())
+    }
+
+    /// Gives up on a saved game's result and next game. The series can still be continued from its results page.
+    #[allow(dead_code)]
+    fn close(&mut self) {
+        self.closed = true;
+        self.version += 1;
+    }
+
+    fn is_fearless_locked(&self, id: u32) -> bool {
+        match self.fearless {
+            FearlessMode::Off => false,
+            FearlessMode::Hard => self.blue_series_picks.contains(&id) || self.red_series_picks.contains(&id),
+            FearlessMode::Soft => match self.turn {
+                Turn::Pick(side, _) => self.series_picks(side).contains(&id),
+                _ => false,
+            },
+        }
+    }
+
+    fn slots(&self, side: Side, action: Action) -> &Vec<Option<u32>> {
+        match (side, action) {
+            (Side::Blue, Action::Ban) => &self.blue_bans,
+            (Side::Red, Action::Ban) => &self.red_bans,
+            (Side::Blue, Action::Pick) => &self.blue_picks,
+            (Side::Red, Action::Pick) => &self.red_picks,
+        }
+    }
+
+    fn slot_mut(&mut self, turn: &Turn) -> Option<&mut Option<u32>> {
+        match turn {
+            Turn::Ban(Side::Blue, index) => self.blue_bans.get_mut(*index),
+            Turn::Ban(Side::Red, index) => self.red_bans.get_mut(*index),
+            Turn::Pick(Side::Blue, index) => self.blue_picks.get_mut(*index),
+            Turn::Pick(Side::Red, index) => self.red_picks.get_mut(*index),
+            _ => None,
+        }
+    }
+
+    /// Every filled slot along with the turn that fills it.
+    fn selections(&self) -> impl Iterator<Item = (Turn, u32)> + '_ {
+        [Side::Blue, Side::Red].into_iter().flat_map(move |side| {
+            let bans = self.slots(side, Action::Ban).iter().enumerate().map(move |(index, pick)| (Turn::Ban(side, index), *pick));
+            let picks = self.slots(side, Action::Pick).iter().enumerate().map(move |(index, pick)| (Turn::Pick(side, index), *pick));
+            bans.chain(picks)
+        }).filter_map(|(turn, pick)| Some((turn, pick?)))
+    }
+
+    /// Whether `id` can't be chosen by `side`, or by the referee or a spectator when `side` is `None`.
+    fn is_champ_chosen(&self, id: u32, side: Option<Side>) -> bool {
+        self.disabled_champions.contains(&id) ||
+        self.is_fearless_locked(id) ||
+        self.selections().any(|(turn, selection_id)| selection_id == id && !self.open_turns(side).contains(&turn))
+    }
+
+    /// The slots whose champions don't count as chosen for `side` yet: the one being filled, or in a blind phase
+    /// the other team's slots, so a team can't learn what the other has locked, along with the one it's filling.
+    /// Blind picks that clash are sorted out by `end_phase`.
+    fn open_turns(&self, side: Option<Side>) -> Vec<Turn> {
+        let Some(side) = side.filter(|_| self.is_simultaneous()) else { return self.phase_turns(); };
+        self.format.phase(self.step)
+            .filter(|step| self.format.steps[*step].side != side || Some(*step) == self.side_step(side))
+            .map(|step| self.format.turn(step))
+            .collect()
+    }
+
+    /// Whether both teams are drafting, as opposed to readying up, done, or editing a sandbox.
+    fn is_started(&self) -> bool {
+        self.blue_ready && self.red_ready && self.turn.side().is_some()
+    }
+
+    /// Whether the current turn is a blind phase, with both teams acting at once.
+    fn is_simultaneous(&self) -> bool {
+        self.is_started() && self.format.phase(self.step).len() > 1
+    }
+
+    /// Every slot of the current phase.
+    fn phase_turns(&self) -> Vec<Turn> {
+        if !self.is_started() {
+            return vec![self.turn];
+        }
+        self.format.phase(self.step).map(|step| self.format.turn(step)).collect()
+    }
+
+    /// The step `side` is acting on: the current one when it's their turn, or their step of a blind phase
+    /// until they lock it in.
+    fn side_step(&self, side: Side) -> Option<usize> {
+        if !self.is_started() {
+            return None;
+        }
+        self.format.phase(self.step).find(|step| self.format.steps[*step].side == side && !self.locked_steps.contains(step))
+    }
+
+    fn side_turn(&self, side: Side) -> Option<Turn> {
+        self.side_step(side).map(|step| self.format.turn(step))
+    }
+
+    fn is_acting(&self, side: Side) -> bool {
+        self.side_step(side).is_some()
+    }
+
+    /// The slots being filled right now, one per team still acting.
+    fn acting_turns(&self) -> Vec<Turn> {
+        if !self.is_started() {
+            return vec![self.turn];
+        }
+        [Side::Blue, Side::Red].into_iter().filter_map(|side| self.side_turn(side)).collect()
+    }
+
+    fn hover(&self, side: Side) -> Option<u32> {
+        self.side_turn(side).and_then(|turn| self.get_pick(&turn))
+    }
+
+    fn hovers(&self) -> Vec<u32> {
+        self.acting_turns().iter().filter_map(|turn| self.get_pick(turn)).collect()
+    }
+
+    /// The image of a slot's champion, or of the seated player's suggestion while the slot is empty.
     fn get_pick_image(&self, turn: Turn) -> String {
-        if let Some(pick_id) = self.get_pick(&turn) {
+        if let Some(pick_id) = self.get_pick(&turn).or(self.suggestion(&turn)) {
             if turn.is_ban() {
                 return format!("https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/champion-icons/{}.png", pick_id)
             } else {
@@ -163,98 +662,935 @@ impl Draft {
 
     fn get_pick(&self, turn: &Turn) -> Option<u32> {
         match turn {
-            Turn::PreDraft => None,
-            Turn::BlueBan1 => self.blue_ban_1,
-            Turn::BlueBan2 => self.blue_ban_2,
-            Turn::BlueBan3 => self.blue_ban_3,
-            Turn::BlueBan4 => self.blue_ban_4,
-            Turn::BlueBan5 => self.blue_ban_5,
-            
-            Turn::RedBan1 => self.red_ban_1,
-            Turn::RedBan2 => self.red_ban_2,
-            Turn::RedBan3 => self.red_ban_3,
-            Turn::RedBan4 => self.red_ban_4,
-            Turn::RedBan5 => self.red_ban_5,
-
-            Turn::BluePick1 => self.blue_pick_1,
-            Turn::BluePick2 => self.blue_pick_2,
-            Turn::BluePick3 => self.blue_pick_3,
-            Turn::BluePick4 => self.blue_pick_4,
-            Turn::BluePick5 => self.blue_pick_5,
-
-            Turn::RedPick1 => self.red_pick_1,
-            Turn::RedPick2 => self.red_pick_2,
-            Turn::RedPick3 => self.red_pick_3,
-            Turn::RedPick4 => self.red_pick_4,
-            Turn::RedPick5 => self.red_pick_5,
-            Turn::PostDraft => None
+            Turn::Ban(side, index) => self.slots(*side, Action::Ban).get(*index).copied().flatten(),
+            Turn::Pick(side, index) => self.slots(*side, Action::Pick).get(*index).copied().flatten(),
+            _ => None,
         }
     }
 
+    /// Who a link opens the draft for.
+    #[allow(dead_code)]
+    fn viewer(&self, link: &Uuid) -> Viewer {
+        if self.is_admin(link) {
+            return Viewer::Referee;
+        }
+        if !self.caster_id.is_nil() && *link == self.caster_id {
+            return Viewer::Caster;
+        }
+        match self.team_side(link) {
+            Some(side) => Viewer::Team(side),
+            None => Viewer::Spectator,
+        }
+    }
+
+    /// Whether `viewer` is kept from seeing `side`'s hovers by the draft's visibility policy.
+    fn hides_hovers(&self, side: Side, viewer: Viewer) -> bool {
+        match (self.hover_visibility, viewer) {
+            (_, Viewer::Referee) => false,
+            (_, Viewer::Team(team)) if team == side => false,
+            (HoverVisibility::VisibleToAll, _) => false,
+            (HoverVisibility::HiddenFromOpponent, viewer) => viewer != Viewer::Caster,
+            (HoverVisibility::HiddenUntilLock, _) => true,
+        }
+    }
+
+    /// The draft as `viewer` should see it. Nobody gets seat tokens or links they weren't given, teams don't
+    /// get each other's priorities or suggestions, and hovers are blanked per the visibility policy. In a
+    /// blind phase only the referee sees anything but their own team's slots until the phase ends.
+    #[allow(dead_code)]
+    fn view_for(&self, viewer: Viewer) -> Draft {
+        let mut view = self.clone();
+        for player in view.blue_players.iter_mut().chain(view.red_players.iter_mut()) {
+            player.token = Uuid::nil();
+        }
+
+        if viewer != Viewer::Referee {
+            view.admin_id = Uuid::nil();
+            if viewer != Viewer::Caster {
+                view.caster_id = Uuid::nil();
+            }
+            for side in [Side::Blue, Side::Red].into_iter().filter(|side| viewer != Viewer::Team(*side)) {
+                match side {
+                    Side::Blue => (view.blue_id, view.blue_priorities) = (Uuid::nil(), vec![]),
+                    Side::Red => (view.red_id, view.red_priorities) = (Uuid::nil(), vec![]),
+                }
+                for player in view.players_mut(side).iter_mut() {
+                    player.suggestion = None;
+                }
+            }
+        }
+
+        for event in view.events.iter_mut().filter(|event| event.kind.is_hover()) {
+            if event.side.is_some_and(|side| self.hides_hovers(side, viewer)) {
+                event.champion = None;
+            }
+        }
+        if self.is_simultaneous() {
+            let hidden: Vec<usize> = self.format.phase(self.step)
+                .filter(|step| viewer != Viewer::Referee && viewer != Viewer::Team(self.format.steps[*step].side))
+                .collect();
+            for step in &hidden {
+                let turn = self.format.turn(*step);
+                if let Some(slot) = view.slot_mut(&turn) {
+                    *slot = None;
+                }
+            }
+            for event in view.events.iter_mut().filter(|event| event.step.is_some_and(|step| hidden.contains(&step))) {
+                event.champion = None;
+            }
+        } else if self.turn.side().is_some_and(|side| self.hides_hovers(side, viewer)) {
+            let turn = self.turn;
+            if let Some(slot) = view.slot_mut(&turn) {
+                *slot = None;
+            }
+        }
+        view
+    }
+
     fn current_pick(&self) -> Option<u32> {
         self.get_pick(&self.turn)
     }
 
+    fn turn_seconds(&self) -> i64 {
+        self.turn.action().map(|action| self.format.timer.turn_seconds(action)).unwrap_or_default()
+    }
+
+    /// Whole seconds left on the current turn as of `now`, for the countdown clients render locally.
+    fn seconds_left(&self, now: DateTime<Utc>) -> i64 {
+        let now = self.clock(now);
+        let Some(deadline) = self.turn_deadline else { return self.turn_seconds(); };
+        let millis_left = (deadline - now).num_milliseconds().max(0);
+        ((millis_left + 999) / 1000).min(self.turn_seconds())
+    }
+
+    /// Whole seconds left before the other team is readied automatically, once one team is ready.
+    fn ready_seconds_left(&self, now: DateTime<Utc>) -> Option<i64> {
+        let now = self.clock(now);
+        let deadline = self.ready_deadline?;
+        let millis_left = (deadline - now).num_milliseconds().max(0);
+        Some((millis_left + 999) / 1000)
+    }
+
+    fn has_bank(&self) -> bool {
+        self.format.timer.bank_seconds > 0
+    }
+
+    fn bank_millis(&self, side: Side) -> i64 {
+        match side {
+            Side::Blue => self.blue_bank_millis,
+            Side::Red => self.red_bank_millis,
+        }
+    }
+
+    /// The team whose time bank the current turn draws on. Blind phases run on the turn timer alone.
+    fn bank_side(&self) -> Option<Side> {
+        self.turn.side().filter(|_| !self.is_simultaneous())
+    }
+
+    /// How much of a team's time bank the current turn has used as of `now`.
+    fn overtime_millis(&self, side: Side, now: DateTime<Utc>) -> i64 {
+        let now = self.clock(now);
+        match (self.bank_side(), self.turn_deadline) {
+            (Some(acting), Some(deadline)) if acting == side => (now - deadline).num_milliseconds().clamp(0, self.bank_millis(side)),
+            _ => 0,
+        }
+    }
+
+    /// Whole seconds left in a team's time bank as of `now`, counting down while its turn is in overtime.
+    fn bank_seconds_left(&self, side: Side, now: DateTime<Utc>) -> i64 {
+        let millis_left = self.bank_millis(side) - self.overtime_millis(side, now);
+        (millis_left + 999) / 1000
+    }
+
+    /// When the server ends the current turn on its own: the deadline, then whatever is left in the acting
+    /// team's time bank, then the format's grace period.
     #[allow(dead_code)]
-    fn select_pick(&mut self, pick: u32) {
-        let pick = Some(pick);
-        match self.turn {
-            Turn::BlueBan1 => self.blue_ban_1 = pick,
-            Turn::BlueBan2 => self.blue_ban_2 = pick,
-            Turn::BlueBan3 => self.blue_ban_3 = pick,
-            Turn::BlueBan4 => self.blue_ban_4 = pick,
-            Turn::BlueBan5 => self.blue_ban_5 = pick,
-            
-            Turn::RedBan1 => self.red_ban_1 = pick,
-            Turn::RedBan2 => self.red_ban_2 = pick,
-            Turn::RedBan3 => self.red_ban_3 = pick,
-            Turn::RedBan4 => self.red_ban_4 = pick,
-            Turn::RedBan5 => self.red_ban_5 = pick,
-
-            Turn::BluePick1 => self.blue_pick_1 = pick,
-            Turn::BluePick2 => self.blue_pick_2 = pick,
-            Turn::BluePick3 => self.blue_pick_3 = pick,
-            Turn::BluePick4 => self.blue_pick_4 = pick,
-            Turn::BluePick5 => self.blue_pick_5 = pick,
-
-            Turn::RedPick1 => self.red_pick_1 = pick,
-            Turn::RedPick2 => self.red_pick_2 = pick,
-            Turn::RedPick3 => self.red_pick_3 = pick,
-            Turn::RedPick4 => self.red_pick_4 = pick,
-            Turn::RedPick5 => self.red_pick_5 = pick,
-            _ => ()
-        }
-    } 
+    fn turn_expires_at(&self) -> Option<DateTime<Utc>> {
+        if self.paused.is_some() {
+            return None;
+        }
+        let bank_millis = self.bank_side().map(|side| self.bank_millis(side)).unwrap_or_default();
+        self.turn_deadline.map(|deadline| deadline + TimeDelta::milliseconds(bank_millis) + TimeDelta::seconds(self.format.timer.grace_seconds))
+    }
+
+    /// The time the clocks read: `now`, or the moment the draft was paused.
+    fn clock(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.paused.map(|pause| pause.at).unwrap_or(now)
+    }
+
+    fn pauses_left(&self, side: Side) -> u32 {
+        let used = match side {
+            Side::Blue => self.blue_pauses_used,
+            Side::Red => self.red_pauses_used,
+        };
+        self.format.timer.pauses.saturating_sub(used)
+    }
 
+    /// Stops every clock. A team's pause counts against its allowance, a referee's (`by` of `None`) doesn't.
     #[allow(dead_code)]
-    fn next_turn(&mut self) {
-        self.turn = match self.turn {
-            Turn::PreDraft => Turn::BlueBan1,
-            Turn::BlueBan1 => Turn::RedBan1,
-            Turn::RedBan1 => Turn::BlueBan2,
-            Turn::BlueBan2 => Turn::RedBan2,
-            Turn::RedBan2 => Turn::BlueBan3,
-            Turn::BlueBan3 => Turn::RedBan3,
-            Turn::RedBan3 => Turn::BluePick1,
-            
-            Turn::BluePick1 => Turn::RedPick1,
-            Turn::RedPick1 => Turn::RedPick2,
-            Turn::RedPick2 => Turn::BluePick2,
-            Turn::BluePick2 => Turn::BluePick3,
-            Turn::BluePick3 => Turn::RedPick3,
-            Turn::RedPick3 => Turn::RedBan4,
-
-            Turn::RedBan4 => Turn::BlueBan4,
-            Turn::BlueBan4 => Turn::RedBan5,
-            Turn::RedBan5 => Turn::BlueBan5,
-            Turn::BlueBan5 => Turn::RedPick4,
-
-            Turn::RedPick4 => Turn::BluePick4,
-            Turn::BluePick4 => Turn::BluePick5,
-            Turn::BluePick5 => Turn::RedPick5,
-            Turn::RedPick5 => Turn::PostDraft,
-            Turn::PostDraft => Turn::PostDraft
+    fn pause(&mut self, by: Option<Side>) {
+        match by {
+            Some(Side::Blue) => self.blue_pauses_used += 1,
+            Some(Side::Red) => self.red_pauses_used += 1,
+            None => (),
+        }
+        self.paused = Some(Pause { by, at: Utc::now() });
+        self.version += 1;
+        self.record(EventKind::Pause, by, None);
+    }
+
+    /// Restarts the clocks, pushing every deadline back by however long the draft was paused.
+    #[allow(dead_code)]
+    fn resume(&mut self, by: Option<Side>) {
+        let Some(pause) = self.paused.take() else { return; };
+        let paused_for = Utc::now() - pause.at;
+        self.turn_deadline = self.turn_deadline.map(|deadline| deadline + paused_for);
+        self.ready_deadline = self.ready_deadline.map(|deadline| deadline + paused_for);
+        self.version += 1;
+        self.record(EventKind::Resume, by, None);
+    }
+
+    /// Marks a team ready, starting the draft once both are. The first team to ready up starts the
+    /// format's ready timeout for the other.
+    #[allow(dead_code)]
+    fn ready(&mut self, side: Side) {
+        match side {
+            Side::Blue => self.blue_ready = true,
+            Side::Red => self.red_ready = true,
+        }
+        self.record(EventKind::Ready, Some(side), None);
+
+        if self.blue_ready && self.red_ready {
+            self.ready_deadline = None;
+            self.next_turn();
+        } else if let Some(ready_seconds) = self.format.timer.ready_seconds {
+            self.ready_deadline.get_or_insert(Utc::now() + TimeDelta::seconds(ready_seconds));
+        }
+    }
+
+    fn priorities(&self, side: Side) -> &Vec<u32> {
+        match side {
+            Side::Blue => &self.blue_priorities,
+            Side::Red => &self.red_priorities,
+        }
+    }
+
+    /// Adds a champion to the end of a team's priorities, or removes it if it's already there.
+    #[allow(dead_code)]
+    fn toggle_priority(&mut self, side: Side, id: u32) {
+        let priorities = match side {
+            Side::Blue => &mut self.blue_priorities,
+            Side::Red => &mut self.red_priorities,
+        };
+        match priorities.iter().position(|priority| *priority == id) {
+            Some(index) => { priorities.remove(index); },
+            None => priorities.push(id),
+        }
+    }
+
+    fn is_auto_filled(&self, turn: &Turn) -> bool {
+        self.auto_filled.iter().any(|step| self.format.turn(*step) == *turn)
+    }
+
+    /// Fills every slot of the phase still open according to the format's timeout policy and moves on. Only
+    /// champions in `champions` that are still available are locked, and `roll` picks one when the policy falls
+    /// back to a random champion. A champion already locked as a pick in a blind phase isn't picked again, so the
+    /// filled slot can't clash with it.
+    #[allow(dead_code)]
+    fn time_out(&mut self, champions: &[u32], roll: usize) {
+        for side in [Side::Blue, Side::Red] {
+            while let Some(step) = self.side_step(side) {
+                self.time_out_step(side, step, champions, roll);
+            }
+        }
+        self.end_phase();
+    }
+
+    fn time_out_step(&mut self, side: Side, step: usize, champions: &[u32], roll: usize) {
+        let turn = self.format.turn(step);
+        let locked_picks: Vec<u32> = self.locked_steps.iter()
+            .map(|step| self.format.turn(*step))
+            .filter(|turn| turn.is_pick())
+            .filter_map(|turn| self.get_pick(&turn))
+            .collect();
+        let is_legal = |id: &u32| champions.contains(id) && !self.is_champ_chosen(*id, Some(side)) && !locked_picks.contains(id);
+        let hover = self.get_pick(&turn).filter(is_legal);
+        let available: Vec<u32> = champions.iter().copied().filter(is_legal).collect();
+        let random = || (!available.is_empty()).then(|| available[roll % available.len()]);
+
+        let pick = match turn {
+            Turn::Ban(_, _) => match self.format.timeout.ban {
+                BanTimeout::LockHover => hover,
+                BanTimeout::Skip => None,
+            },
+            Turn::Pick(_, _) => match self.format.timeout.pick {
+                PickTimeout::LockHover => hover.or_else(random),
+                PickTimeout::Random => random(),
+                PickTimeout::Priority => self.priorities(side).iter().copied()
+                    .find(is_legal)
+                    .or_else(random),
+            },
+            _ => return,
+        };
+
+        if let Some(slot) = self.slot_mut(&turn) {
+            *slot = pick;
+        }
+        self.auto_filled.push(step);
+        self.locked_steps.push(step);
+        self.record_step(EventKind::Timeout, Some(side), Some(step), pick);
+    }
+
+    /// The draft as it stood after `events`, replayed onto empty slots. Used to step through a completed draft.
+    fn replayed(&self, events: &[DraftEvent]) -> Draft {
+        let mut draft = Draft {
+            step: 0,
+            turn: Turn::PreDraft,
+            auto_filled: vec![],
+            locked_steps: vec![],
+            ..self.clone()
         };
+        draft.clear_slots_from(self.branch_step.unwrap_or(0));
+
+        let (mut blue_ready, mut red_ready) = (false, false);
+        for event in events {
+            if event.kind.is_ready() {
+                blue_ready |= event.side.is_some_and(|side| side.is_blue());
+                red_ready |= event.side.is_some_and(|side| side.is_red());
+                if blue_ready && red_ready && draft.turn.is_pre_draft() {
+                    draft.step = self.branch_step.unwrap_or(0);
+                    draft.turn = self.format.turn(draft.step);
+                }
+                continue;
+            }
+            if event.kind.is_reset() {
+                (blue_ready, red_ready) = (false, false);
+                draft.clear_slots_from(self.branch_step.unwrap_or(0));
+                draft.auto_filled.clear();
+                draft.step = 0;
+                draft.turn = Turn::PreDraft;
+                continue;
+            }
+
+            let Some(step) = event.step else { continue; };
+            let turn = self.format.turn(step);
+            match event.kind {
+                EventKind::Hover => {
+                    if let Some(slot) = draft.slot_mut(&turn) {
+                        *slot = event.champion;
+                    }
+                    draft.step = step;
+                    draft.turn = turn;
+                }
+                EventKind::Lock | EventKind::Timeout => {
+                    if let Some(slot) = draft.slot_mut(&turn) {
+                        *slot = event.champion;
+                    }
+                    if event.kind.is_timeout() {
+                        draft.auto_filled.push(step);
+                    }
+                    // Teams lock a blind phase in either order, so a lock never moves the draft backwards.
+                    draft.step = draft.step.max(step + 1);
+                    draft.turn = self.format.turn(draft.step);
+                }
+                EventKind::Clash => {
+                    if let Some(slot) = draft.slot_mut(&turn) {
+                        *slot = None;
+                    }
+                    draft.auto_filled.retain(|auto_filled| *auto_filled != step);
+                    draft.step = self.format.phase(step).start;
+                    draft.turn = self.format.turn(draft.step);
+                }
+                EventKind::Revert => {
+                    for current in self.format.phase(draft.step).map(|step| self.format.turn(step)) {
+                        if let Some(slot) = draft.slot_mut(&current) {
+                            *slot = None;
+                        }
+                    }
+                    draft.auto_filled.retain(|auto_filled| *auto_filled != step);
+                    draft.step = step;
+                    draft.turn = turn;
+                }
+                EventKind::Set => {
+                    if let Some(slot) = draft.slot_mut(&turn) {
+                        *slot = event.champion;
+                    }
+                    draft.auto_filled.retain(|auto_filled| *auto_filled != step);
+                }
+                _ => (),
+            }
+        }
+        draft
+    }
+
+    /// A draft without teams or timers that one person edits freely, slot by slot.
+    #[allow(dead_code)]
+    fn sandbox(format: DraftFormat) -> Draft {
+        let mut draft = Draft::new(Uuid::nil(), Uuid::nil(), Uuid::nil(), format);
+        draft.turn = draft.format.turn(0);
+        draft
+    }
+
+    /// Makes `turn` the slot being edited.
+    #[allow(dead_code)]
+    fn focus(&mut self, turn: Turn) {
+        if let Some(step) = (0..self.format.steps.len()).find(|step| self.format.turn(*step) == turn) {
+            self.step = step;
+            self.turn = turn;
+        }
+    }
+
+    /// Sets or clears the slot being edited. A filled slot moves the focus on to the next empty one.
+    #[allow(dead_code)]
+    fn set_slot(&mut self, pick: Option<u32>) {
+        let turn = self.turn;
+        let Some(slot) = self.slot_mut(&turn) else { return; };
+        *slot = pick;
+        if pick.is_none() {
+            return;
+        }
+
+        let steps = self.format.steps.len();
+        let next_empty = (1..steps)
+            .map(|offset| self.format.turn((self.step + offset) % steps))
+            .find(|turn| self.get_pick(turn).is_none());
+        if let Some(next_empty) = next_empty {
+            self.focus(next_empty);
+        }
+    }
+
+    /// The last locked step, while the draft is still running and it isn't before the point the draft was forked at.
+    fn undoable_step(&self) -> Option<usize> {
+        (self.turn.side().is_some() && self.step > self.branch_step.unwrap_or(0)).then(|| self.format.phase(self.step - 1).start)
+    }
+
+    /// The undo request the other team can still approve.
+    fn pending_undo(&self, now: DateTime<Utc>) -> Option<UndoRequest> {
+        self.undo_request.filter(|request| request.expires > now && Some(request.step) == self.undoable_step())
+    }
+
+    #[allow(dead_code)]
+    fn request_undo(&mut self, side: Side, step: usize) {
+        let expires = Utc::now() + TimeDelta::seconds(UNDO_WINDOW_SECONDS);
+        self.undo_request = Some(UndoRequest { side, step, expires });
+    }
+
+    /// Rolls the draft back to the requested step.
+    #[allow(dead_code)]
+    fn undo(&mut self) {
+        let Some(request) = self.undo_request.take() else { return; };
+        self.revert(request.step, Some(request.side));
+    }
+
+    /// Rolls the draft back to the phase at `step`. Its champions stay hovered and the turn timer starts over.
+    #[allow(dead_code)]
+    fn revert(&mut self, step: usize, side: Option<Side>) {
+        self.undo_request = None;
+        for turn in self.phase_turns() {
+            if let Some(slot) = self.slot_mut(&turn) {
+                *slot = None;
+            }
+        }
+        self.step = step;
+        self.turn = self.format.turn(self.step);
+        self.locked_steps.clear();
+        self.auto_filled.retain(|auto_filled| !self.format.phase(step).contains(auto_filled));
+        self.version += 1;
+        self.turn_deadline = Some(Utc::now() + TimeDelta::seconds(self.turn_seconds()));
+        self.record(EventKind::Revert, side, self.current_pick());
+    }
+
+    fn is_admin(&self, team_id: &Uuid) -> bool {
+        !self.admin_id.is_nil() && *team_id == self.admin_id
+    }
+
+    /// Runs a referee's change, flagging every event it records for the audit trail.
+    #[allow(dead_code)]
+    fn as_referee(&mut self, change: impl FnOnce(&mut Draft)) {
+        let recorded = self.events.len();
+        change(self);
+        for event in &mut self.events[recorded..] {
+            event.referee = true;
+        }
+    }
+
+    /// Fills or clears any slot without moving the draft along.
+    #[allow(dead_code)]
+    fn override_slot(&mut self, turn: Turn, pick: Option<u32>) {
+        let Some(step) = (0..self.format.steps.len()).find(|step| self.format.turn(*step) == turn) else { return; };
+        let Some(slot) = self.slot_mut(&turn) else { return; };
+        *slot = pick;
+        self.auto_filled.retain(|auto_filled| *auto_filled != step);
+        self.version += 1;
+        self.record_step(EventKind::Set, turn.side(), Some(step), pick);
     }
 
-}
\ No newline at end of file
+    fn clear_slots_from(&mut self, from: usize) {
+        for step in from..self.format.steps.len() {
+            let turn = self.format.turn(step);
+            if let Some(slot) = self.slot_mut(&turn) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Empties the slots after the branch point and sends the draft back to waiting on both teams to ready up.
+    #[allow(dead_code)]
+    fn reset(&mut self) {
+        self.clear_slots_from(self.branch_step.unwrap_or(0));
+        self.blue_ready = false;
+        self.red_ready = false;
+        self.step = 0;
+        self.turn = Turn::PreDraft;
+        self.turn_deadline = None;
+        self.ready_deadline = None;
+        self.undo_request = None;
+        self.paused = None;
+        self.auto_filled.clear();
+        self.locked_steps.clear();
+        self.blue_bank_millis = self.format.timer.bank_seconds * 1000;
+        self.red_bank_millis = self.format.timer.bank_seconds * 1000;
+        self.version += 1;
+        self.record(EventKind::Reset, None, None);
+    }
+
+    fn record(&mut self, kind: EventKind, side: Option<Side>, champion: Option<u32>) {
+        let step = self.turn.side().map(|_| self.step);
+        self.record_step(kind, side, step, champion);
+    }
+
+    fn record_step(&mut self, kind: EventKind, side: Option<Side>, step: Option<usize>, champion: Option<u32>) {
+        self.events.push(DraftEvent { kind, side, step, champion, at: Utc::now(), referee: false });
+    }
+
+    #[allow(dead_code)]
+    fn select_pick(&mut self, side: Side, pick: u32) {
+        let Some(step) = self.side_step(side) else { return; };
+        let turn = self.format.turn(step);
+        if let Some(slot) = self.slot_mut(&turn) {
+            *slot = Some(pick);
+            self.record_step(EventKind::Hover, Some(side), Some(step), Some(pick));
+        }
+    }
+
+    /// Locks in whatever a team has hovered, moving on once every team in the phase has.
+    #[allow(dead_code)]
+    fn lock_in(&mut self, side: Side) {
+        let Some(step) = self.side_step(side) else { return; };
+        self.lock_step(step);
+        self.end_phase();
+    }
+
+    /// Locks in the hover of every team still acting, for a referee moving the draft along.
+    #[allow(dead_code)]
+    fn advance(&mut self) {
+        for step in self.format.phase(self.step) {
+            if !self.locked_steps.contains(&step) {
+                self.lock_step(step);
+            }
+        }
+        self.end_phase();
+    }
+
+    fn lock_step(&mut self, step: usize) {
+        let turn = self.format.turn(step);
+        self.locked_steps.push(step);
+        self.record_step(EventKind::Lock, turn.side(), Some(step), self.get_pick(&turn));
+    }
+
+    /// Moves on once every step of the current phase is locked. Two teams can't both have a champion they
+    /// picked blind, so the later pick in turn order is sent back to be picked again on a fresh timer. Matching
+    /// blind bans both stand, as in solo queue.
+    fn end_phase(&mut self) {
+        let phase = self.format.phase(self.step);
+        if !phase.clone().all(|step| self.locked_steps.contains(&step)) {
+            return;
+        }
+
+        let mut picked: Vec<u32> = vec![];
+        let mut clashes: Vec<usize> = vec![];
+        for step in phase {
+            let turn = self.format.turn(step);
+            let Some(pick) = self.get_pick(&turn).filter(|_| turn.is_pick()) else { continue; };
+            if picked.contains(&pick) {
+                clashes.push(step);
+            } else {
+                picked.push(pick);
+            }
+        }
+        if clashes.is_empty() {
+            self.next_turn();
+            return;
+        }
+
+        for step in clashes {
+            let turn = self.format.turn(step);
+            if let Some(slot) = self.slot_mut(&turn) {
+                *slot = None;
+            }
+            self.locked_steps.retain(|locked| *locked != step);
+            self.auto_filled.retain(|auto_filled| *auto_filled != step);
+            self.record_step(EventKind::Clash, turn.side(), Some(step), None);
+        }
+        self.version += 1;
+        self.turn_deadline = Some(Utc::now() + TimeDelta::seconds(self.turn_seconds()));
+    }
+
+    #[allow(dead_code)]
+    fn next_turn(&mut self) {
+        let now = Utc::now();
+        if let Some(side) = self.bank_side() {
+            let overtime = self.overtime_millis(side, now);
+            match side {
+                Side::Blue => self.blue_bank_millis -= overtime,
+                Side::Red => self.red_bank_millis -= overtime,
+            }
+        }
+
+        self.undo_request = None;
+        self.locked_steps.clear();
+        match self.turn {
+            Turn::PreDraft => self.step = self.branch_step.unwrap_or(0),
+            Turn::PostDraft => return,
+            _ => self.step = self.format.phase(self.step).end,
+        }
+        self.turn = self.format.turn(self.step);
+        self.version += 1;
+        self.turn_deadline = (!self.turn.is_post_draft())
+            .then(|| now + TimeDelta::seconds(self.turn_seconds()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+    use uuid::Uuid;
+    use crate::format::{BanTimeout, DraftFormat, FormatPreset, HoverVisibility, PickTimeout, Side};
+    use crate::event::EventKind;
+    use crate::series::{FearlessMode, TeamInfo};
+    use crate::{Draft, SeatPass, Turn, Viewer, UNDO_WINDOW_SECONDS};
+
+    const VIEWERS: [Viewer; 5] = [Viewer::Spectator, Viewer::Caster, Viewer::Team(Side::Blue), Viewer::Team(Side::Red), Viewer::Referee];
+
+    /// A tournament draft whose first two picks are taken blind, started and played through its first bans.
+    fn blind_first_pick() -> Draft {
+        let mut format = DraftFormat::from(FormatPreset::Tournament);
+        format.steps[7].with_previous = true;
+        through_bans(started(format))
+    }
+
+    /// Locks champions 1 to 6 in the tournament draft's first ban phase, leaving it on blue's first pick.
+    fn through_bans(mut draft: Draft) -> Draft {
+        for (step, ban) in (1..=6).enumerate() {
+            let side = if step % 2 == 0 { Side::Blue } else { Side::Red };
+            draft.select_pick(side, ban);
+            draft.lock_in(side);
+        }
+        draft
+    }
+
+    fn pass() -> SeatPass {
+        SeatPass { player_id: Uuid::new_v4(), token: Uuid::new_v4() }
+    }
+
+    fn started(format: DraftFormat) -> Draft {
+        let mut draft = Draft::new(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), format);
+        draft.admin_id = Uuid::new_v4();
+        draft.caster_id = Uuid::new_v4();
+        draft.ready(Side::Blue);
+        draft.ready(Side::Red);
+        draft
+    }
+
+    #[test]
+    fn locked_blind_pick_stays_hidden() {
+        let mut draft = blind_first_pick();
+        assert_eq!(draft.phase_turns(), vec![Turn::Pick(Side::Blue, 0), Turn::Pick(Side::Red, 0)]);
+
+        draft.select_pick(Side::Blue, 10);
+        draft.lock_in(Side::Blue);
+
+        assert!(!draft.is_champ_chosen(10, Some(Side::Red)));
+        assert!(draft.is_champ_chosen(10, Some(Side::Blue)));
+        assert_eq!(draft.acting_turns(), vec![Turn::Pick(Side::Red, 0)]);
+    }
+
+    #[test]
+    fn blind_clash_sends_later_pick_back() {
+        let mut draft = blind_first_pick();
+        draft.select_pick(Side::Blue, 10);
+        draft.lock_in(Side::Blue);
+        draft.select_pick(Side::Red, 10);
+        draft.lock_in(Side::Red);
+
+        assert_eq!(draft.step, 6);
+        assert_eq!(draft.blue_picks[0], Some(10));
+        assert_eq!(draft.red_picks[0], None);
+        assert!(draft.events.last().is_some_and(|event| event.kind == EventKind::Clash && event.side == Some(Side::Red)));
+
+        draft.select_pick(Side::Red, 11);
+        draft.lock_in(Side::Red);
+
+        assert_eq!(draft.step, 8);
+        assert_eq!(draft.red_picks[0], Some(11));
+    }
+
+    #[test]
+    fn blind_time_out_skips_locked_pick() {
+        let mut draft = blind_first_pick();
+        draft.select_pick(Side::Blue, 10);
+        draft.lock_in(Side::Blue);
+        draft.select_pick(Side::Red, 10);
+
+        draft.time_out(&[10, 11], 0);
+
+        assert_eq!(draft.step, 8);
+        assert_eq!(draft.blue_picks[0], Some(10));
+        assert_eq!(draft.red_picks[0], Some(11));
+    }
+
+    #[test]
+    fn ranked_time_out_fills_every_ban() {
+        let mut draft = started(FormatPreset::Ranked.into());
+        draft.select_pick(Side::Blue, 1);
+        draft.lock_in(Side::Blue);
+        draft.select_pick(Side::Red, 1);
+
+        draft.time_out(&[1, 2, 3], 0);
+
+        assert_eq!(draft.step, 10);
+        assert_eq!(draft.turn, Turn::Pick(Side::Blue, 0));
+        assert_eq!(draft.blue_bans, vec![Some(1), None, None, None, None]);
+        assert_eq!(draft.red_bans, vec![Some(1), None, None, None, None]);
+    }
+
+    #[test]
+    fn hovers_follow_visibility() {
+        for visibility in [HoverVisibility::VisibleToAll, HoverVisibility::HiddenFromOpponent, HoverVisibility::HiddenUntilLock] {
+            let mut draft = started(FormatPreset::Tournament.into());
+            draft.hover_visibility = visibility;
+            draft.select_pick(Side::Blue, 5);
+
+            for viewer in VIEWERS {
+                let sees = match (visibility, viewer) {
+                    (_, Viewer::Referee | Viewer::Team(Side::Blue)) => true,
+                    (HoverVisibility::VisibleToAll, _) => true,
+                    (HoverVisibility::HiddenFromOpponent, viewer) => viewer == Viewer::Caster,
+                    (HoverVisibility::HiddenUntilLock, _) => false,
+                };
+                let view = draft.view_for(viewer);
+                let hover = sees.then_some(5);
+                assert_eq!(view.blue_bans[0], hover, "{visibility:?} {viewer:?}");
+                assert!(view.events.iter().filter(|event| event.kind.is_hover()).all(|event| event.champion == hover), "{visibility:?} {viewer:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn locked_slots_are_seen_by_everyone() {
+        let mut draft = started(FormatPreset::Tournament.into());
+        draft.hover_visibility = HoverVisibility::HiddenUntilLock;
+        draft.select_pick(Side::Blue, 5);
+        draft.lock_in(Side::Blue);
+
+        for viewer in VIEWERS {
+            assert_eq!(draft.view_for(viewer).blue_bans[0], Some(5), "{viewer:?}");
+        }
+    }
+
+    #[test]
+    fn views_only_carry_their_own_secrets() {
+        let mut draft = started(FormatPreset::Tournament.into());
+        draft.join(Side::Blue, SeatPass { player_id: Uuid::new_v4(), token: Uuid::new_v4() }, String::from("Blue"));
+        draft.toggle_priority(Side::Blue, 5);
+        draft.toggle_priority(Side::Red, 6);
+
+        for viewer in VIEWERS {
+            let view = draft.view_for(viewer);
+            assert!(view.blue_players.iter().all(|player| player.token.is_nil()), "{viewer:?}");
+            assert_eq!(!view.admin_id.is_nil(), viewer == Viewer::Referee, "{viewer:?}");
+            assert_eq!(!view.caster_id.is_nil(), matches!(viewer, Viewer::Referee | Viewer::Caster), "{viewer:?}");
+            for side in [Side::Blue, Side::Red] {
+                let own = matches!(viewer, Viewer::Referee) || viewer == Viewer::Team(side);
+                assert_eq!(!view.priorities(side).is_empty(), own, "{viewer:?} {side:?}");
+                let link = match side {
+                    Side::Blue => view.blue_id,
+                    Side::Red => view.red_id,
+                };
+                assert_eq!(!link.is_nil(), own, "{viewer:?} {side:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn blind_slots_are_hidden_from_everyone_but_their_team() {
+        for visibility in [HoverVisibility::VisibleToAll, HoverVisibility::HiddenFromOpponent, HoverVisibility::HiddenUntilLock] {
+            let mut draft = blind_first_pick();
+            draft.hover_visibility = visibility;
+            draft.select_pick(Side::Blue, 10);
+            draft.lock_in(Side::Blue);
+
+            for viewer in VIEWERS {
+                let sees = matches!(viewer, Viewer::Referee | Viewer::Team(Side::Blue));
+                assert_eq!(draft.view_for(viewer).blue_picks[0], sees.then_some(10), "{visibility:?} {viewer:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn own_blind_bans_count_as_chosen() {
+        let mut draft = started(FormatPreset::Ranked.into());
+        draft.select_pick(Side::Blue, 1);
+        draft.lock_in(Side::Blue);
+
+        assert!(draft.is_champ_chosen(1, Some(Side::Blue)));
+        assert!(!draft.is_champ_chosen(1, Some(Side::Red)));
+        assert_eq!(draft.side_step(Side::Blue), Some(2));
+    }
+
+    #[test]
+    fn revert_reopens_the_last_locked_step() {
+        let mut draft = started(FormatPreset::Tournament.into());
+        draft.select_pick(Side::Blue, 1);
+        draft.lock_in(Side::Blue);
+        draft.select_pick(Side::Red, 2);
+
+        assert_eq!(draft.undoable_step(), Some(0));
+        draft.revert(0, None);
+
+        assert_eq!(draft.turn, Turn::Ban(Side::Blue, 0));
+        assert_eq!(draft.blue_bans[0], Some(1));
+        assert_eq!(draft.red_bans[0], None);
+        assert!(draft.locked_steps.is_empty());
+        assert!(draft.events.last().is_some_and(|event| event.kind == EventKind::Revert));
+        assert_eq!(draft.undoable_step(), None);
+    }
+
+    #[test]
+    fn undo_request_expires_and_lapses_once_the_draft_moves_on() {
+        let mut draft = started(FormatPreset::Tournament.into());
+        draft.select_pick(Side::Blue, 1);
+        draft.lock_in(Side::Blue);
+        draft.request_undo(Side::Blue, 0);
+
+        assert!(draft.pending_undo(Utc::now()).is_some());
+        assert!(draft.pending_undo(Utc::now() + TimeDelta::seconds(UNDO_WINDOW_SECONDS + 1)).is_none());
+
+        let mut moved_on = draft.clone();
+        moved_on.select_pick(Side::Red, 2);
+        moved_on.lock_in(Side::Red);
+        assert!(moved_on.pending_undo(Utc::now()).is_none());
+
+        draft.undo();
+        assert_eq!(draft.turn, Turn::Ban(Side::Blue, 0));
+        assert!(draft.undo_request.is_none());
+        assert!(draft.events.last().is_some_and(|event| event.kind == EventKind::Revert && event.side == Some(Side::Blue)));
+    }
+
+    #[test]
+    fn ban_time_out_follows_policy() {
+        for (policy, ban) in [(BanTimeout::LockHover, Some(3)), (BanTimeout::Skip, None)] {
+            let mut format = DraftFormat::from(FormatPreset::Tournament);
+            format.timeout.ban = policy;
+            let mut draft = started(format);
+            draft.select_pick(Side::Blue, 3);
+
+            draft.time_out(&[1, 2, 3], 0);
+
+            assert_eq!(draft.blue_bans[0], ban, "{policy:?}");
+            assert_eq!(draft.auto_filled, vec![0], "{policy:?}");
+            assert_eq!(draft.turn, Turn::Ban(Side::Red, 0), "{policy:?}");
+        }
+    }
+
+    #[test]
+    fn pick_time_out_follows_policy() {
+        let champions: Vec<u32> = (1..=12).collect();
+        for (policy, pick) in [(PickTimeout::LockHover, 10), (PickTimeout::Random, 7), (PickTimeout::Priority, 9)] {
+            let mut format = DraftFormat::from(FormatPreset::Tournament);
+            format.timeout.pick = policy;
+            let mut draft = through_bans(started(format));
+            draft.toggle_priority(Side::Blue, 2);
+            draft.toggle_priority(Side::Blue, 9);
+            draft.select_pick(Side::Blue, 10);
+
+            draft.time_out(&champions, 0);
+
+            assert_eq!(draft.blue_picks[0], Some(pick), "{policy:?}");
+        }
+    }
+
+    #[test]
+    fn pick_time_out_never_locks_a_taken_hover() {
+        let mut draft = through_bans(started(FormatPreset::Tournament.into()));
+        draft.select_pick(Side::Blue, 1);
+
+        draft.time_out(&[1, 7], 0);
+
+        assert_eq!(draft.blue_picks[0], Some(7));
+    }
+
+    #[test]
+    fn fearless_locks_earlier_picks() {
+        let mut game = through_bans(started(FormatPreset::Tournament.into()));
+        game.blue_team = TeamInfo { name: String::from("One"), ..Default::default() };
+        game.red_team = TeamInfo { name: String::from("Two"), ..Default::default() };
+        game.blue_picks[0] = Some(20);
+        game.red_picks[0] = Some(21);
+
+        // Blue is team Two now, so 21 is its own earlier pick and 20 the other team's.
+        for (fearless, own_locked, other_locked) in [(FearlessMode::Off, false, false), (FearlessMode::Hard, true, true), (FearlessMode::Soft, true, false)] {
+            let mut draft = started(FormatPreset::Tournament.into());
+            draft.blue_team = game.red_team.clone();
+            draft.red_team = game.blue_team.clone();
+            draft.join_series(Uuid::new_v4(), fearless, vec![game.clone()]);
+            let draft = through_bans(draft);
+            assert_eq!(draft.turn, Turn::Pick(Side::Blue, 0));
+
+            assert_eq!(draft.game_number, 2);
+            assert_eq!(draft.is_champ_chosen(21, Some(Side::Blue)), own_locked, "{fearless:?}");
+            assert_eq!(draft.is_champ_chosen(20, Some(Side::Blue)), other_locked, "{fearless:?}");
+        }
+    }
+
+    #[test]
+    fn captain_acts_on_every_turn_and_players_on_their_own_pick() {
+        let mut draft = through_bans(started(FormatPreset::Tournament.into()));
+        assert!(draft.can_act(Side::Blue, None));
+
+        let (captain, player) = (pass(), pass());
+        draft.join(Side::Blue, captain, String::from("Captain"));
+        draft.join(Side::Blue, player, String::from("Player"));
+
+        assert!(draft.is_captain(Side::Blue, Some(&captain.token)));
+        assert!(draft.can_act(Side::Blue, Some(&captain.token)));
+        assert!(!draft.can_act(Side::Blue, Some(&player.token)));
+        assert!(!draft.can_act(Side::Blue, None));
+        assert!(!draft.can_act(Side::Blue, Some(&Uuid::new_v4())));
+        assert!(draft.can_act(Side::Red, None));
+
+        assert!(!draft.make_captain(Side::Blue, &Uuid::new_v4()));
+        assert!(draft.make_captain(Side::Blue, &player.player_id));
+        assert!(draft.is_captain(Side::Blue, Some(&player.token)));
+        assert!(!draft.is_captain(Side::Blue, Some(&captain.token)));
+        // The old captain holds the first seat, so this pick is still theirs.
+        assert!(draft.can_act(Side::Blue, Some(&captain.token)));
+
+        draft.release(Side::Blue);
+        assert!(draft.players(Side::Blue).is_empty());
+        assert!(draft.can_act(Side::Blue, None));
+    }
+
+    #[test]
+    fn relinked_team_link_only_spectates() {
+        let mut draft = started(FormatPreset::Tournament.into());
+        let old_link = draft.blue_id;
+        let new_link = Uuid::new_v4();
+
+        draft.relink(Side::Blue, new_link);
+
+        assert_eq!(draft.viewer(&old_link), Viewer::Spectator);
+        assert_eq!(draft.viewer(&new_link), Viewer::Team(Side::Blue));
+        assert_eq!(draft.view_for(draft.viewer(&old_link)).blue_id, Uuid::nil());
+    }
+}
@@ -0,0 +1,264 @@
+use chrono::{Local, Utc};
+use leptos::logging::error;
+use leptos::prelude::*;
+use leptos_ws::ServerSignal;
+use sea_orm::{sea_query, DatabaseConnection, DbErr, EntityTrait, Set};
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+use crate::entity::{champion, draft, draft_event, live_draft};
+use crate::registry::DraftViews;
+use crate::format::Side;
+use crate::{AppState, Draft};
+
+/// Publishes a new draft's signals and spawns the tasks that run it.
+pub fn start_draft(draft: Draft, app_state: &AppState) {
+    run_draft(draft, app_state.clone());
+}
+
+/// Restarts every draft that was still in progress when the server stopped, so teams can reconnect with their links.
+pub async fn restore_drafts(app_state: &AppState) -> Result<(), DbErr> {
+    let live_drafts = live_draft::Entity::find().all(&app_state.db).await?;
+
+    let owner = Owner::new();
+    owner.with(|| {
+        provide_context(app_state.server_signals.clone());
+        for live_draft in live_drafts {
+            match serde_json::from_str::<Draft>(&live_draft.state) {
+                Ok(draft) => run_draft(draft, app_state.clone()),
+                Err(err) => error!("Couldn't restore draft {}: {}", live_draft.draft_id, err),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Cancels a draft and clears its snapshot so it isn't restored on the next start. Its events are kept.
+pub async fn cancel_draft(app_state: &AppState, draft_id: Uuid) -> Result<(), DbErr> {
+    if let Some(signal) = app_state.registry.signal(&draft_id) {
+        persist_events(&app_state.db, &signal.get(), 0).await?;
+    }
+    live_draft::Entity::delete_by_id(draft_id.to_string()).exec(&app_state.db).await?;
+    app_state.registry.cancel(&draft_id);
+    Ok(())
+}
+
+/// Ends each turn by the format's timeout policy once its deadline and grace period pass, publishes every
+/// role's view and snapshots the draft on every change, and saves it once it's complete. The snapshot is kept until
+/// the draft is finished, so a series can still be reported after a restart, and the draft is then dropped from the
+/// registry. Drafts that sit in `PreDraft` without any change for longer than the registry's idle TTL are cancelled,
+/// and saved games whose series waits that long on a result or the next game are closed without them.
+fn run_draft(draft: Draft, app_state: AppState) {
+    let db = app_state.db.clone();
+    let draft_id = draft.draft_id;
+    // Clients only ever get the filtered views, so the full state lives under a name nobody is given.
+    let draft_signal = ServerSignal::new(Uuid::new_v4().to_string(), draft.clone()).unwrap();
+    let views = DraftViews::new(app_state.server_signals.clone());
+    views.publish(&draft);
+
+    draft_signal.update(move |value| *value = draft);
+
+    let mut view_subscription = draft_signal.subscribe();
+    let view_signal = draft_signal.clone();
+    let draft_views = views.clone();
+    let view_task = tokio::spawn(async move {
+        loop {
+            match view_subscription.recv().await {
+                Ok(_) | Err(RecvError::Lagged(_)) => draft_views.publish(&view_signal.get()),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let mut snapshot_subscription = draft_signal.subscribe();
+    let snapshot_signal = draft_signal.clone();
+    let snapshot_db = db.clone();
+    let snapshot_registry = app_state.registry.clone();
+    let snapshot_task = tokio::spawn(async move {
+        // Restored drafts start from zero again, which is harmless since inserting an event twice is a no-op.
+        let mut persisted_events = 0;
+        loop {
+            let draft = snapshot_signal.get();
+            if draft.draft_id.is_nil() {
+                break;
+            }
+
+            match persist_events(&snapshot_db, &draft, persisted_events).await {
+                Ok(persisted) => persisted_events = persisted,
+                Err(db_err) => error!("Couldn't persist events of draft {}: {}", draft_id, db_err),
+            }
+
+            // A saved draft in a series still takes its result and the next game from the live state, so it's
+            // kept until there's nothing left to report.
+            if draft.is_finished() {
+                if let Err(db_err) = live_draft::Entity::delete_by_id(draft_id.to_string()).exec(&snapshot_db).await {
+                    error!("Couldn't clear snapshot of draft {}: {}", draft_id, db_err);
+                }
+                snapshot_registry.finish(&draft_id);
+                break;
+            }
+
+            if let Err(db_err) = snapshot_draft(&snapshot_db, &draft).await {
+                error!("Couldn't snapshot draft {}: {}", draft_id, db_err);
+            }
+
+            match snapshot_subscription.recv().await {
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let mut draft_subscription = draft_signal.subscribe();
+    let registry_signal = draft_signal.clone();
+    let registry = app_state.registry.clone();
+    let idle_ttl = registry.idle_ttl();
+    let draft_task = tokio::spawn(async move {
+        // A referee can send the draft back to `PreDraft`, which starts it over from waiting on the teams.
+        'draft: loop {
+            loop {
+                let draft = draft_signal.get();
+
+                if draft.blue_ready && draft.red_ready {
+                    break;
+                }
+
+                let ready_expired = async move {
+                    match draft.ready_deadline.filter(|_| draft.paused.is_none()) {
+                        Some(ready_deadline) => tokio::time::sleep((ready_deadline - Utc::now()).to_std().unwrap_or_default()).await,
+                        None => std::future::pending().await,
+                    }
+                };
+
+                tokio::select! {
+                    _ = ready_expired => draft_signal.update(move |value| {
+                        if !(value.blue_ready && value.red_ready) && value.paused.is_none() {
+                            value.ready(Side::Blue);
+                            value.ready(Side::Red);
+                        }
+                    }),
+                    received = tokio::time::timeout(idle_ttl, draft_subscription.recv()) => match received {
+                        Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => continue,
+                        Ok(Err(RecvError::Closed)) => return,
+                        Err(_) => {
+                            if let Err(db_err) = cancel_draft(&app_state, draft_id).await {
+                                error!("Couldn't clear snapshot of abandoned draft {}: {}", draft_id, db_err);
+                                app_state.registry.cancel(&draft_id);
+                            }
+                            return;
+                        }
+                    },
+                }
+            }
+
+            loop {
+                let draft = draft_signal.get();
+
+                if draft.turn.is_post_draft() {
+                    break 'draft;
+                }
+                if draft.turn.is_pre_draft() {
+                    continue 'draft;
+                }
+
+                let version = draft.version;
+                let expired = async move {
+                    match draft.turn_expires_at() {
+                        Some(expires_at) => tokio::time::sleep((expires_at - Utc::now()).to_std().unwrap_or_default()).await,
+                        None => std::future::pending().await,
+                    }
+                };
+
+                tokio::select! {
+                    _ = expired => {
+                        let champions = match champion::Entity::find().all(&db).await {
+                            Ok(champions) => champions.into_iter().map(|champion| champion.id).collect(),
+                            Err(db_err) => {
+                                error!("Couldn't load champions for draft {}: {}", draft_id, db_err);
+                                vec![]
+                            }
+                        };
+                        let roll = Uuid::new_v4().as_u128() as usize;
+                        draft_signal.update(move |value| {
+                            if value.version == version {
+                                value.time_out(&champions, roll);
+                            }
+                        });
+                    },
+                    received = draft_subscription.recv() => if let Err(RecvError::Closed) = received {
+                        return;
+                    },
+                }
+            }
+        }
+
+        if let Err(db_err) = save_draft(&db, draft_signal.get()).await {
+            error!("Couldn't save draft {}: {}", draft_id, db_err);
+            return;
+        }
+        draft_signal.update(move |value| value.saved = true);
+
+        loop {
+            if draft_signal.get().is_finished() {
+                return;
+            }
+            match tokio::time::timeout(idle_ttl, draft_subscription.recv()).await {
+                Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => continue,
+                Ok(Err(RecvError::Closed)) => return,
+                Err(_) => {
+                    draft_signal.update(|value| value.close());
+                    return;
+                }
+            }
+        }
+    });
+
+    registry.register(draft_id, registry_signal, views, vec![view_task.abort_handle(), snapshot_task.abort_handle(), draft_task.abort_handle()]);
+}
+
+/// Writes the live state of a draft that's still in progress, replacing its previous snapshot.
+async fn snapshot_draft(db: &DatabaseConnection, draft: &Draft) -> Result<(), DbErr> {
+    let state = serde_json::to_string(draft).map_err(|err| DbErr::Custom(err.to_string()))?;
+    let snapshot = live_draft::ActiveModel {
+        draft_id: Set(draft.draft_id.to_string()),
+        state: Set(state),
+        updated_at: Set(Local::now().naive_local()),
+    };
+
+    live_draft::Entity::insert(snapshot)
+        .on_conflict(
+            sea_query::OnConflict::column(live_draft::Column::DraftId)
+                .update_columns([live_draft::Column::State, live_draft::Column::UpdatedAt])
+                .to_owned()
+        )
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Appends the events after the first `from` to the draft's event log and returns how many are now stored.
+async fn persist_events(db: &DatabaseConnection, draft: &Draft, from: usize) -> Result<usize, DbErr> {
+    let Some(events) = draft.events.get(from..).filter(|events| !events.is_empty()) else {
+        return Ok(from);
+    };
+
+    let events = events.iter().enumerate().map(|(index, event)| {
+        let model: draft_event::ActiveModel = event.to_model(draft.draft_id.to_string(), (from + index) as u32).into();
+        model
+    });
+    draft_event::Entity::insert_many(events)
+        .on_conflict_do_nothing()
+        .exec(db)
+        .await?;
+    Ok(draft.events.len())
+}
+
+/// Writes a completed draft. Saving a draft that's already stored leaves the stored row as is.
+pub async fn save_draft(db: &DatabaseConnection, draft: Draft) -> Result<(), DbErr> {
+    let draft: draft::ActiveModel = draft::Model::from(draft).into();
+    draft::Entity::insert(draft)
+        .on_conflict_do_nothing()
+        .exec(db)
+        .await?;
+    Ok(())
+}
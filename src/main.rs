@@ -10,7 +10,7 @@ cfg_if! { if #[cfg(feature = "ssr")] {
     use sea_orm::Database;
     use dotenv::dotenv;
     use std::env;
-    use lol_draft::{app::*, AppState};
+    use lol_draft::{app::*, registry::DraftRegistry, AppState};
     use leptos_ws::server_signals::ServerSignals;
 
     #[tokio::main]
@@ -31,11 +31,12 @@ cfg_if! { if #[cfg(feature = "ssr")] {
         let routes = generate_route_list(App);
 
         let server_signals = ServerSignals::new();
-
         let app_state = AppState {
             db: db.clone(),
             server_signals: server_signals.clone(),
+            registry: DraftRegistry::from_env(),
         };
+        lol_draft::lifecycle::restore_drafts(&app_state).await.expect("Couldn't restore live drafts.");
 
         let app = Router::new()
             .route(
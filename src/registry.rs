@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::{Local, NaiveDateTime};
+use leptos::prelude::*;
+use leptos_ws::server_signals::ServerSignals;
+use leptos_ws::ServerSignal;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+use crate::{Draft, Turn, Viewer};
+
+/// How long a draft may sit in `PreDraft`, or wait on its series once saved, without any activity before it's
+/// dropped, unless `DRAFT_IDLE_TTL_SECS` says otherwise.
+const DEFAULT_IDLE_TTL: Duration = Duration::from_secs(60 * 60);
+
+struct LiveDraft {
+    signal: ServerSignal<Draft>,
+    views: DraftViews,
+    tasks: Vec<AbortHandle>,
+    started: NaiveDateTime,
+}
+
+/// A draft this server is still running, as listed by `DraftRegistry::live_drafts`.
+#[derive(Clone, Debug)]
+pub struct LiveDraftInfo {
+    pub draft_id: Uuid,
+    pub turn: Turn,
+    pub started: NaiveDateTime,
+}
+
+/// The filtered copies of a draft that clients subscribe to. The spectator view is published under the draft's
+/// id and every link's view, the caster's included, under `{draft_id}/{link}`, so knowing a link is what gets a
+/// client its view.
+#[derive(Clone)]
+pub(crate) struct DraftViews {
+    server_signals: ServerSignals,
+    views: Arc<Mutex<HashMap<String, (Option<Uuid>, ServerSignal<Draft>)>>>,
+}
+
+impl DraftViews {
+    pub(crate) fn new(server_signals: ServerSignals) -> Self {
+        DraftViews {
+            server_signals,
+            views: Default::default(),
+        }
+    }
+
+    /// Updates every view from the draft's full state. A link that's been replaced keeps its signal, but
+    /// only gets the spectator view from then on.
+    pub(crate) fn publish(&self, draft: &Draft) {
+        let mut views = self.views.lock().unwrap();
+        for link in [None, Some(draft.blue_id), Some(draft.red_id), Some(draft.admin_id), Some(draft.caster_id)] {
+            if link.is_some_and(|link| link.is_nil()) {
+                continue;
+            }
+            let name = match link {
+                Some(link) => format!("{}/{}", draft.draft_id, link),
+                None => draft.draft_id.to_string(),
+            };
+            if !views.contains_key(&name) {
+                // Views are published from the draft's tasks, which run outside any reactive owner.
+                let signal = Owner::new().with(|| {
+                    provide_context(self.server_signals.clone());
+                    ServerSignal::new(name.clone(), Draft::default())
+                });
+                match signal {
+                    Ok(signal) => { views.insert(name, (link, signal)); }
+                    Err(err) => leptos::logging::error!("Couldn't publish view {}: {:?}", name, err),
+                }
+            }
+        }
+
+        for (link, signal) in views.values() {
+            let viewer = link.map(|link| draft.viewer(&link)).unwrap_or(Viewer::Spectator);
+            let view = draft.view_for(viewer);
+            signal.update(move |value| *value = view);
+        }
+    }
+
+    fn clear(&self) {
+        for (_, signal) in self.views.lock().unwrap().values() {
+            signal.update(|value| *value = Draft::default());
+        }
+    }
+}
+
+/// Every draft with live signals on this server, along with the tasks running it.
+#[derive(Clone)]
+pub struct DraftRegistry {
+    drafts: Arc<Mutex<HashMap<Uuid, LiveDraft>>>,
+    idle_ttl: Duration,
+}
+
+impl DraftRegistry {
+    pub fn new(idle_ttl: Duration) -> Self {
+        DraftRegistry {
+            drafts: Default::default(),
+            idle_ttl,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let idle_ttl = env::var("DRAFT_IDLE_TTL_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_IDLE_TTL);
+        Self::new(idle_ttl)
+    }
+
+    pub fn idle_ttl(&self) -> Duration {
+        self.idle_ttl
+    }
+
+    pub(crate) fn register(&self, draft_id: Uuid, signal: ServerSignal<Draft>, views: DraftViews, tasks: Vec<AbortHandle>) {
+        let live_draft = LiveDraft {
+            signal,
+            views,
+            tasks,
+            started: Local::now().naive_local(),
+        };
+        self.drafts.lock().unwrap().insert(draft_id, live_draft);
+    }
+
+    pub fn contains(&self, draft_id: &Uuid) -> bool {
+        self.drafts.lock().unwrap().contains_key(draft_id)
+    }
+
+    pub fn signal(&self, draft_id: &Uuid) -> Option<ServerSignal<Draft>> {
+        self.drafts.lock().unwrap().get(draft_id).map(|live_draft| live_draft.signal.clone())
+    }
+
+    /// The live game of a series with the given number, if it has been started.
+    pub(crate) fn series_game(&self, series_id: &Uuid, game_number: u32) -> Option<Draft> {
+        self.drafts.lock().unwrap().values()
+            .map(|live_draft| live_draft.signal.get())
+            .find(|draft| draft.series_id.as_ref() == Some(series_id) && draft.game_number == game_number)
+    }
+
+    pub fn live_drafts(&self) -> Vec<LiveDraftInfo> {
+        let mut live_drafts: Vec<LiveDraftInfo> = self.drafts.lock().unwrap().iter().map(|(draft_id, live_draft)| LiveDraftInfo {
+            draft_id: *draft_id,
+            turn: live_draft.signal.get().turn,
+            started: live_draft.started,
+        }).collect();
+        live_drafts.sort_by_key(|info| info.started);
+        live_drafts
+    }
+
+    /// Drops a draft once there's nothing left to do with it, after giving its views the final state to
+    /// send clients on to the results. Its snapshot has already been cleared by the task calling this.
+    pub(crate) fn finish(&self, draft_id: &Uuid) {
+        let Some(live_draft) = self.drafts.lock().unwrap().remove(draft_id) else { return; };
+        live_draft.views.publish(&live_draft.signal.get());
+        live_draft.tasks.iter().for_each(|task| task.abort());
+    }
+
+    /// Stops a draft's tasks and drops it. `ServerSignals` can't unregister a signal, so it and its views are
+    /// reset to the empty draft that `check_for_draft` treats as missing.
+    pub fn cancel(&self, draft_id: &Uuid) {
+        let Some(live_draft) = self.drafts.lock().unwrap().remove(draft_id) else { return; };
+        live_draft.tasks.iter().for_each(|task| task.abort());
+        live_draft.signal.update(|value| *value = Draft::default());
+        live_draft.views.clear();
+    }
+}
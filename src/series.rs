@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIs, EnumIter, EnumString};
+
+#[derive(Default, Display, EnumIter, EnumString, PartialEq, Eq, EnumIs, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum FearlessMode {
+    #[default]
+    Off,
+    /// Champions picked earlier in the series are unavailable to both teams.
+    Hard,
+    /// Each team is only locked out of the champions it picked earlier in the series.
+    Soft,
+}
+
+#[derive(Default, Display, EnumIter, EnumString, PartialEq, Eq, EnumIs, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum SideSelection {
+    /// Teams swap sides every game.
+    #[default]
+    Alternate,
+    /// The team that lost the previous game chooses its side.
+    LoserPicks,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TeamInfo {
+    pub name: String,
+    /// A short tag shown in place of the name where space is tight.
+    #[serde(default)]
+    pub tag: String,
+    /// An image URL.
+    #[serde(default)]
+    pub logo: Option<String>,
+}
+
+/// How a new series is set up. `best_of` is `None` for open-ended series that are continued by hand.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SeriesOptions {
+    pub fearless: FearlessMode,
+    pub best_of: Option<u32>,
+    pub side_selection: SideSelection,
+    pub team_one: TeamInfo,
+    pub team_two: TeamInfo,
+}
+
+impl SeriesOptions {
+    pub fn is_series(&self) -> bool {
+        !self.fearless.is_off() || self.best_of.is_some_and(|best_of| best_of > 1)
+    }
+}
+
+cfg_if::cfg_if! { if #[cfg(feature = "ssr")] {
+    use std::str::FromStr;
+    use sea_orm::*;
+    use uuid::Uuid;
+    use crate::entity::{draft, series};
+    use crate::{AppState, Draft};
+
+    /// The series' saved games in the order they were played.
+    pub async fn series_games(db: &DatabaseConnection, series_id: &str) -> Result<Vec<Draft>, DbErr> {
+        let games = draft::Entity::find()
+            .filter(draft::Column::SeriesId.eq(series_id))
+            .order_by_asc(draft::Column::GameNumber)
+            .all(db)
+            .await?;
+        Ok(games.into_iter().map(Draft::from).collect())
+    }
+
+    /// Starts the game after `previous` and returns its id.
+    pub async fn start_next_game(app_state: &AppState, previous: &Draft, swap_sides: bool) -> Result<Uuid, DbErr> {
+        let db = &app_state.db;
+        let series_id = previous.series_id.ok_or(DbErr::RecordNotFound("Draft is not part of a series.".to_string()))?;
+        let series = series::Entity::find_by_id(series_id.to_string()).one(db).await?
+            .ok_or(DbErr::RecordNotFound("Series not found.".to_string()))?;
+
+        let mut draft = previous.next_game(Uuid::new_v4(), swap_sides);
+        let games = series_games(db, &series.series_id).await?;
+        draft.join_series(series_id, FearlessMode::from_str(&series.fearless).unwrap_or_default(), games);
+
+        let draft_id = draft.draft_id;
+        crate::lifecycle::start_draft(draft, app_state);
+        Ok(draft_id)
+    }
+}}